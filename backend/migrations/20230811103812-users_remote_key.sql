@@ -0,0 +1,5 @@
+ALTER TABLE "users"
+    ADD COLUMN "uri" TEXT NULL,
+    ADD COLUMN "key_id" TEXT NULL;
+CREATE UNIQUE INDEX "users_unique_uri" ON "users" ("uri");
+CREATE UNIQUE INDEX "users_unique_key_id" ON "users" ("key_id");
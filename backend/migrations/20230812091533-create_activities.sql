@@ -0,0 +1,9 @@
+CREATE TABLE "activities" (
+    "id" TEXT COLLATE "C" NOT NULL PRIMARY KEY,
+    "user_id" TEXT NOT NULL REFERENCES "users" ("id"),
+    "activity_type" TEXT NOT NULL,
+    "body" JSONB NOT NULL,
+    "public" BOOLEAN NOT NULL,
+    "published_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX "activities_outbox" ON "activities" ("user_id", "id" DESC) WHERE "public";
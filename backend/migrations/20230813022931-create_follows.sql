@@ -0,0 +1,10 @@
+CREATE TABLE "follows" (
+    "id" TEXT COLLATE "C" NOT NULL PRIMARY KEY,
+    "follower_id" TEXT NOT NULL REFERENCES "users" ("id"),
+    "followee_id" TEXT NOT NULL REFERENCES "users" ("id"),
+    "state" TEXT NOT NULL CHECK ("state" IN ('pending', 'accepted')),
+    "activity_id" TEXT NOT NULL,
+    "created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE UNIQUE INDEX "follows_unique_pair" ON "follows" ("follower_id", "followee_id");
+CREATE UNIQUE INDEX "follows_unique_activity_id" ON "follows" ("activity_id");
@@ -0,0 +1,10 @@
+CREATE TABLE "notes" (
+    "id" TEXT COLLATE "C" NOT NULL PRIMARY KEY,
+    "user_id" TEXT NOT NULL REFERENCES "users" ("id"),
+    "content" TEXT NOT NULL,
+    "published_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "in_reply_to" TEXT NULL,
+    "to" JSONB NOT NULL DEFAULT '[]',
+    "cc" JSONB NOT NULL DEFAULT '[]'
+);
+CREATE INDEX "notes_user_id" ON "notes" ("user_id", "id" DESC);
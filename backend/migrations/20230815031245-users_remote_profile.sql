@@ -0,0 +1,4 @@
+ALTER TABLE "users"
+    ADD COLUMN "shared_inbox" TEXT NULL,
+    ADD COLUMN "avatar_url" TEXT NULL,
+    ADD COLUMN "fetched_at" TIMESTAMPTZ NULL;
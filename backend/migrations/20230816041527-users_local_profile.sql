@@ -0,0 +1,4 @@
+ALTER TABLE "users"
+    ADD COLUMN "header_url" TEXT NULL,
+    ADD COLUMN "manually_approves_followers" BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN "profile_fields" JSONB NOT NULL DEFAULT '[]';
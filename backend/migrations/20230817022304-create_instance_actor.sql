@@ -0,0 +1,6 @@
+CREATE TABLE "instance_actor" (
+    "singleton" BOOLEAN NOT NULL PRIMARY KEY DEFAULT TRUE CHECK ("singleton"),
+    "created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "public_key" TEXT NOT NULL,
+    "private_key" TEXT NOT NULL
+);
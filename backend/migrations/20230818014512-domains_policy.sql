@@ -0,0 +1,3 @@
+ALTER TABLE "domains"
+    ADD COLUMN "policy" TEXT NOT NULL DEFAULT 'none',
+    ADD COLUMN "policy_reason" TEXT NULL;
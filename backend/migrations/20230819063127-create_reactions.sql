@@ -0,0 +1,21 @@
+CREATE TABLE "likes" (
+    "id" TEXT COLLATE "C" NOT NULL PRIMARY KEY,
+    "user_id" TEXT NOT NULL REFERENCES "users" ("id"),
+    "object_uri" TEXT NOT NULL,
+    "activity_id" TEXT NOT NULL,
+    "created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE UNIQUE INDEX "likes_unique_pair" ON "likes" ("user_id", "object_uri");
+CREATE UNIQUE INDEX "likes_unique_activity_id" ON "likes" ("activity_id");
+CREATE INDEX "likes_object_uri" ON "likes" ("object_uri");
+
+CREATE TABLE "announces" (
+    "id" TEXT COLLATE "C" NOT NULL PRIMARY KEY,
+    "user_id" TEXT NOT NULL REFERENCES "users" ("id"),
+    "object_uri" TEXT NOT NULL,
+    "activity_id" TEXT NOT NULL,
+    "created_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE UNIQUE INDEX "announces_unique_pair" ON "announces" ("user_id", "object_uri");
+CREATE UNIQUE INDEX "announces_unique_activity_id" ON "announces" ("activity_id");
+CREATE INDEX "announces_object_uri" ON "announces" ("object_uri");
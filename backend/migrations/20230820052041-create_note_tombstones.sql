@@ -0,0 +1,4 @@
+CREATE TABLE "note_tombstones" (
+    "id" TEXT COLLATE "C" NOT NULL PRIMARY KEY,
+    "deleted_at" TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
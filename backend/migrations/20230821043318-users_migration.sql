@@ -0,0 +1,3 @@
+ALTER TABLE "users"
+    ADD COLUMN "also_known_as" JSONB NOT NULL DEFAULT '[]',
+    ADD COLUMN "moved_to" TEXT NULL;
@@ -0,0 +1,7 @@
+ALTER TABLE "likes"
+    ADD COLUMN "author_uri" TEXT NULL,
+    ADD COLUMN "author_inbox" TEXT NULL;
+
+ALTER TABLE "announces"
+    ADD COLUMN "author_uri" TEXT NULL,
+    ADD COLUMN "author_inbox" TEXT NULL;
@@ -0,0 +1,23 @@
+use serde_json::Value as JsonValue;
+use time::OffsetDateTime;
+
+/// Activity performed by a local user.
+#[derive(Debug, Clone)]
+pub struct Activity {
+    pub id: String,
+    pub user_id: String,
+    pub activity_type: String,
+    pub body: JsonValue,
+    pub public: bool,
+    pub published: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct ActivityRegistration {
+    /// Order58 ID, which is also used for the activity URL.
+    pub id: String,
+    pub user_id: String,
+    pub activity_type: String,
+    pub body: JsonValue,
+    pub public: bool,
+}
@@ -1,3 +1,11 @@
+/// JSON-LD term resolution with bundled contexts.
+pub mod context;
+
+/// ActivityStreams 2.0 vocabulary. Documents in the wild are loosely shaped,
+/// so most properties accept either a single value or an array, and either
+/// an IRI or an embedded object.
+pub mod vocabulary;
+
 use crate::user::{validate_username_format, UsernameError};
 
 use thiserror::Error as ThisError;
@@ -62,3 +70,13 @@ impl Acct {
         format!("acct:{}@{}", self.username, self.origin)
     }
 }
+
+/// Extracts acct origin part from URL, which may contain port part.
+/// Returned value will be like `example.com` or `localhost:3000`.
+pub fn acct_origin_of(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    match url.port() {
+        Some(port) => Some(format!("{host}:{port}")),
+        None => Some(host.to_string()),
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+pub const ACTIVITYSTREAMS_CONTEXT_URL: &str = "https://www.w3.org/ns/activitystreams";
+pub const SECURITY_V1_CONTEXT_URL: &str = "https://w3id.org/security/v1";
+
+/// Limit of nested term references, to avoid cyclic definitions.
+const EXPANSION_DEPTH_LIMIT: usize = 8;
+
+static ACTIVITYSTREAMS_CONTEXT: Lazy<JsonValue> = Lazy::new(|| {
+    serde_json::from_str(include_str!("../../contexts/activitystreams.jsonld"))
+        .expect("invalid bundled context")
+});
+
+static SECURITY_V1_CONTEXT: Lazy<JsonValue> = Lazy::new(|| {
+    serde_json::from_str(include_str!("../../contexts/security-v1.jsonld"))
+        .expect("invalid bundled context")
+});
+
+/// Widely used extension terms, treated as canonical together with
+/// activitystreams and security ones.
+static EXTENSION_CONTEXT: Lazy<JsonValue> = Lazy::new(|| {
+    json!({
+        "as": "https://www.w3.org/ns/activitystreams#",
+        "toot": "http://joinmastodon.org/ns#",
+        "schema": "http://schema.org#",
+        "alsoKnownAs": { "@id": "as:alsoKnownAs", "@type": "@id" },
+        "movedTo": { "@id": "as:movedTo", "@type": "@id" },
+        "discoverable": "toot:discoverable",
+        "indexable": "toot:indexable",
+        "suspended": "toot:suspended",
+        "featured": { "@id": "toot:featured", "@type": "@id" },
+        "featuredTags": { "@id": "toot:featuredTags", "@type": "@id" },
+        "Emoji": "toot:Emoji",
+        "PropertyValue": "schema:PropertyValue",
+        "value": "schema:value",
+    })
+});
+
+/// Terms of bundled contexts, used as the canonical names.
+static CANONICAL_CONTEXT: Lazy<CanonicalContext> = Lazy::new(|| {
+    let mut context = ActiveContext::default();
+    context.merge(&ACTIVITYSTREAMS_CONTEXT["@context"]);
+    context.merge(&SECURITY_V1_CONTEXT["@context"]);
+    context.merge(&EXTENSION_CONTEXT);
+    CanonicalContext::new(context)
+});
+
+/// Returns bundled copy of the remote context document.
+/// Contexts are never fetched from network.
+pub fn bundled_context(url: &str) -> Option<&'static JsonValue> {
+    let url = url.trim_end_matches('#').trim_end_matches(".jsonld");
+    let url = url.strip_prefix("http://").unwrap_or(url);
+    let url = url.strip_prefix("https://").unwrap_or(url);
+    if Some(url) == ACTIVITYSTREAMS_CONTEXT_URL.strip_prefix("https://") {
+        Some(&ACTIVITYSTREAMS_CONTEXT)
+    } else if Some(url) == SECURITY_V1_CONTEXT_URL.strip_prefix("https://") {
+        Some(&SECURITY_V1_CONTEXT)
+    } else {
+        None
+    }
+}
+
+/// Rewrites property names and `type` values of the document into canonical terms,
+/// according to `@context` of the document. For example, `as:Public` in `to` becomes
+/// full IRI and `toot:discoverable` becomes `discoverable`.
+/// Unknown terms are left as they are.
+pub fn canonicalize(document: JsonValue) -> JsonValue {
+    let mut context = ActiveContext::default();
+    if document.get("@context").is_none() {
+        context.merge(&ACTIVITYSTREAMS_CONTEXT["@context"]);
+    }
+    canonicalize_value(document, &context, None)
+}
+
+fn canonicalize_value(value: JsonValue, context: &ActiveContext, key: Option<&str>) -> JsonValue {
+    match value {
+        JsonValue::Object(object) => JsonValue::Object(canonicalize_object(object, context)),
+        JsonValue::Array(values) => values
+            .into_iter()
+            .map(|v| canonicalize_value(v, context, key))
+            .collect(),
+        JsonValue::String(iri) => {
+            let canonical = CANONICAL_CONTEXT.definition(key);
+            match canonical {
+                Some(d) if d.iri == "@type" => context
+                    .expand(&iri)
+                    .and_then(|e| CANONICAL_CONTEXT.compact(&e))
+                    .map(String::from)
+                    .unwrap_or(iri)
+                    .into(),
+                Some(d) if d.id_typed => context.expand_prefixed(&iri).unwrap_or(iri).into(),
+                _ => iri.into(),
+            }
+        }
+        otherwise => otherwise,
+    }
+}
+
+fn canonicalize_object(
+    object: JsonMap<String, JsonValue>,
+    context: &ActiveContext,
+) -> JsonMap<String, JsonValue> {
+    let local_context = object.get("@context").map(|local| {
+        let mut merged = context.clone();
+        merged.merge(local);
+        merged
+    });
+    let context = local_context.as_ref().unwrap_or(context);
+
+    let mut canonicalized = JsonMap::with_capacity(object.len());
+    for (key, value) in object {
+        if key == "@context" {
+            canonicalized.insert(key, value);
+            continue;
+        }
+        let key = CANONICAL_CONTEXT.compact_key(&key, context);
+        let value = canonicalize_value(value, context, Some(&key));
+        canonicalized.insert(key, value);
+    }
+    canonicalized
+}
+
+#[derive(Debug, Clone)]
+struct TermDefinition {
+    iri: String,
+    id_typed: bool,
+    container: bool,
+}
+
+/// Term definitions accumulated from `@context`.
+#[derive(Debug, Clone, Default)]
+struct ActiveContext {
+    terms: HashMap<String, TermDefinition>,
+}
+
+impl ActiveContext {
+    fn merge(&mut self, context: &JsonValue) {
+        match context {
+            JsonValue::Null => self.terms.clear(),
+            JsonValue::String(url) => {
+                if let Some(bundled) = bundled_context(url) {
+                    self.merge(&bundled["@context"]);
+                }
+            }
+            JsonValue::Array(contexts) => {
+                for context in contexts {
+                    self.merge(context);
+                }
+            }
+            JsonValue::Object(definitions) => {
+                for (term, definition) in definitions {
+                    if term.starts_with('@') {
+                        continue;
+                    }
+                    let definition = match definition {
+                        JsonValue::String(iri) => TermDefinition {
+                            iri: iri.clone(),
+                            id_typed: false,
+                            container: false,
+                        },
+                        JsonValue::Object(expanded) => {
+                            let Some(iri) = expanded.get("@id").and_then(|i| i.as_str()) else {
+                                continue;
+                            };
+                            TermDefinition {
+                                iri: iri.to_string(),
+                                id_typed: expanded.get("@type").and_then(|t| t.as_str())
+                                    == Some("@id"),
+                                container: expanded.contains_key("@container"),
+                            }
+                        }
+                        _ => continue,
+                    };
+                    self.terms.insert(term.clone(), definition);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Expands a term or compact IRI into absolute IRI (or keyword).
+    fn expand(&self, value: &str) -> Option<String> {
+        self.expand_within(value, EXPANSION_DEPTH_LIMIT)
+    }
+
+    /// Expands only compact IRIs like `as:Public`.
+    fn expand_prefixed(&self, value: &str) -> Option<String> {
+        let (prefix, _) = value.split_once(':')?;
+        if !self.terms.contains_key(prefix) {
+            return None;
+        }
+        self.expand(value)
+    }
+
+    fn expand_within(&self, value: &str, depth: usize) -> Option<String> {
+        if depth == 0 {
+            return None;
+        }
+        if value.starts_with('@') {
+            return Some(value.to_string());
+        }
+        if let Some(definition) = self.terms.get(value) {
+            if definition.iri == value {
+                return Some(value.to_string());
+            }
+            return self.expand_within(&definition.iri, depth - 1);
+        }
+
+        let (prefix, suffix) = value.split_once(':')?;
+        if suffix.starts_with("//") {
+            return Some(value.to_string());
+        }
+        match self.terms.get(prefix) {
+            Some(definition) => {
+                let namespace = self.expand_within(&definition.iri, depth - 1)?;
+                Some(format!("{namespace}{suffix}"))
+            }
+            None => Some(value.to_string()),
+        }
+    }
+}
+
+/// Bundled terms with reverse lookup table.
+struct CanonicalContext {
+    context: ActiveContext,
+    reverse: HashMap<String, String>,
+}
+
+impl CanonicalContext {
+    fn new(context: ActiveContext) -> CanonicalContext {
+        let mut terms: Vec<_> = context.terms.iter().collect();
+        terms.sort_by_key(|&(term, _)| term);
+
+        let mut reverse = HashMap::new();
+        for (term, definition) in terms {
+            if definition.container {
+                continue;
+            }
+            if let Some(iri) = context.expand(term) {
+                reverse.entry(iri).or_insert_with(|| term.clone());
+            }
+        }
+        CanonicalContext { context, reverse }
+    }
+
+    fn definition(&self, term: Option<&str>) -> Option<&TermDefinition> {
+        self.context.terms.get(term?)
+    }
+
+    fn compact(&self, iri: &str) -> Option<&str> {
+        self.reverse.get(iri).map(|t| t.as_str())
+    }
+
+    fn compact_key(&self, key: &str, context: &ActiveContext) -> String {
+        let Some(expanded) = context.expand(key) else {
+            return key.to_string();
+        };
+        if self.context.terms.contains_key(key)
+            && self.context.expand(key).as_deref() == Some(&expanded)
+        {
+            return key.to_string();
+        }
+        self.compact(&expanded).unwrap_or(key).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::canonicalize;
+
+    use serde_json::json;
+
+    #[test]
+    fn canonicalize_resolves_prefixed_terms() {
+        let document = canonicalize(json!({
+            "@context": [
+                "https://www.w3.org/ns/activitystreams",
+                {
+                    "toot": "http://joinmastodon.org/ns#",
+                    "nsfw": "as:sensitive",
+                },
+            ],
+            "id": "https://remote.example/notes/1",
+            "type": "as:Note",
+            "to": ["as:Public"],
+            "toot:discoverable": true,
+            "nsfw": false,
+            "_misskey_content": "text",
+        }));
+        assert_eq!(document["type"], "Note");
+        assert_eq!(
+            document["to"],
+            json!(["https://www.w3.org/ns/activitystreams#Public"])
+        );
+        assert_eq!(document["discoverable"], true);
+        assert_eq!(document["sensitive"], false);
+        assert_eq!(document["_misskey_content"], "text");
+    }
+
+    #[test]
+    fn canonicalize_accepts_single_context() {
+        let document = canonicalize(json!({
+            "@context": { "as": "https://www.w3.org/ns/activitystreams#" },
+            "as:actor": "https://remote.example/users/alice",
+            "as:object": { "type": "as:Follow" },
+        }));
+        assert_eq!(document["actor"], "https://remote.example/users/alice");
+        assert_eq!(document["object"]["type"], "Follow");
+    }
+}
@@ -0,0 +1,635 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use url::Url;
+
+/// Value of a property which may be either a single value or an array.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    Many(Vec<T>),
+    One(T),
+}
+
+impl<T> OneOrMany<T> {
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        match self {
+            OneOrMany::Many(values) => values.iter(),
+            OneOrMany::One(value) => std::slice::from_ref(value).iter(),
+        }
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            OneOrMany::Many(values) => values.is_empty(),
+            OneOrMany::One(_) => false,
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::Many(values) => values,
+            OneOrMany::One(value) => vec![value],
+        }
+    }
+}
+
+impl<T> Default for OneOrMany<T> {
+    fn default() -> Self {
+        OneOrMany::Many(vec![])
+    }
+}
+
+impl<T> From<T> for OneOrMany<T> {
+    fn from(value: T) -> Self {
+        OneOrMany::One(value)
+    }
+}
+
+impl<T> From<Vec<T>> for OneOrMany<T> {
+    fn from(values: Vec<T>) -> Self {
+        OneOrMany::Many(values)
+    }
+}
+
+/// Value of a property which may be either an IRI or an embedded object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum IriOrObject<T> {
+    Iri(Url),
+    Object(Box<T>),
+}
+
+impl<T: Identified> IriOrObject<T> {
+    /// IRI of the value. Embedded objects may be anonymous.
+    pub fn id(&self) -> Option<&Url> {
+        match self {
+            IriOrObject::Iri(iri) => Some(iri),
+            IriOrObject::Object(object) => object.id(),
+        }
+    }
+}
+
+impl<T> IriOrObject<T> {
+    pub fn as_object(&self) -> Option<&T> {
+        match self {
+            IriOrObject::Iri(_) => None,
+            IriOrObject::Object(object) => Some(object),
+        }
+    }
+}
+
+impl<T> From<Url> for IriOrObject<T> {
+    fn from(iri: Url) -> Self {
+        IriOrObject::Iri(iri)
+    }
+}
+
+/// Types which have (or can have) an IRI.
+pub trait Identified {
+    fn id(&self) -> Option<&Url>;
+}
+
+/// Any type of object, tried in order of specificity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnyObject {
+    Activity(Box<Activity>),
+    Actor(Box<Actor>),
+    Collection(Box<Collection>),
+    CollectionPage(Box<CollectionPage>),
+    Link(Link),
+    PropertyValue(PropertyValue),
+    Object(Box<Object>),
+}
+
+impl Identified for AnyObject {
+    fn id(&self) -> Option<&Url> {
+        match self {
+            AnyObject::Activity(activity) => activity.id(),
+            AnyObject::Actor(actor) => actor.id(),
+            AnyObject::Collection(collection) => collection.id(),
+            AnyObject::CollectionPage(page) => page.id(),
+            AnyObject::Link(link) => link.id(),
+            AnyObject::PropertyValue(_) => None,
+            AnyObject::Object(object) => object.id(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectKind {
+    Article,
+    Audio,
+    Document,
+    Event,
+    Image,
+    Note,
+    Page,
+    Place,
+    Profile,
+    Question,
+    Relationship,
+    Tombstone,
+    Video,
+
+    /// Extension types which are not known.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Object other than actors, activities, collections and links.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Object {
+    #[serde(rename = "type")]
+    pub kind: ObjectKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<OneOrMany<IriOrObject<Link>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributed_to: Option<OneOrMany<IriOrObject<AnyObject>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<IriOrObject<Object>>,
+
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub published: Option<OffsetDateTime>,
+
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated: Option<OffsetDateTime>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<OneOrMany<Url>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cc: Option<OneOrMany<Url>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<OneOrMany<AnyObject>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<OneOrMany<AnyObject>>,
+}
+
+impl Object {
+    pub fn new(kind: ObjectKind) -> Object {
+        Object {
+            kind,
+            id: None,
+            name: None,
+            summary: None,
+            content: None,
+            media_type: None,
+            url: None,
+            attributed_to: None,
+            in_reply_to: None,
+            published: None,
+            updated: None,
+            to: None,
+            cc: None,
+            sensitive: None,
+            tag: None,
+            attachment: None,
+        }
+    }
+
+    /// Iterates over `to` and `cc`.
+    pub fn addressees(&self) -> impl Iterator<Item = &Url> {
+        self.to.iter().chain(&self.cc).flat_map(|a| a.iter())
+    }
+}
+
+impl Identified for Object {
+    fn id(&self) -> Option<&Url> {
+        self.id.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActorKind {
+    Application,
+    Group,
+    Organization,
+    Person,
+    Service,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Actor {
+    #[serde(rename = "type")]
+    pub kind: ActorKind,
+
+    pub id: Url,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<OneOrMany<IriOrObject<Link>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<OneOrMany<IriOrObject<AnyObject>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<OneOrMany<IriOrObject<AnyObject>>>,
+
+    pub inbox: Url,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outbox: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub followers: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub following: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoints: Option<ActorEndpoints>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<PublicKey>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manually_approves_followers: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discoverable: Option<bool>,
+
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub published: Option<OffsetDateTime>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<OneOrMany<AnyObject>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub also_known_as: Option<OneOrMany<Url>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved_to: Option<Url>,
+}
+
+impl Actor {
+    pub fn new(kind: ActorKind, id: Url, inbox: Url) -> Actor {
+        Actor {
+            kind,
+            id,
+            preferred_username: None,
+            name: None,
+            summary: None,
+            url: None,
+            icon: None,
+            image: None,
+            inbox,
+            outbox: None,
+            followers: None,
+            following: None,
+            endpoints: None,
+            public_key: None,
+            manually_approves_followers: None,
+            discoverable: None,
+            published: None,
+            attachment: None,
+            also_known_as: None,
+            moved_to: None,
+        }
+    }
+
+    /// Checks whether the actor declares `uri` as its alias.
+    pub fn is_also_known_as(&self, uri: &str) -> bool {
+        self.also_known_as
+            .as_ref()
+            .is_some_and(|aliases| aliases.iter().any(|a| a.as_str() == uri))
+    }
+}
+
+impl Identified for Actor {
+    fn id(&self) -> Option<&Url> {
+        Some(&self.id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActorEndpoints {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_inbox: Option<Url>,
+}
+
+/// Public key of the actor, defined in security vocabulary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKey {
+    pub id: String,
+    pub owner: Url,
+    pub public_key_pem: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActivityKind {
+    Accept,
+    Announce,
+    Block,
+    Create,
+    Delete,
+    Flag,
+    Follow,
+    Like,
+    Move,
+    Reject,
+    Undo,
+    Update,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    #[serde(rename = "type")]
+    pub kind: ActivityKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Url>,
+
+    pub actor: IriOrObject<Actor>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<OneOrMany<IriOrObject<AnyObject>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<IriOrObject<AnyObject>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub published: Option<OffsetDateTime>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<OneOrMany<Url>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cc: Option<OneOrMany<Url>>,
+}
+
+impl Activity {
+    pub fn new(kind: ActivityKind, actor: IriOrObject<Actor>) -> Activity {
+        Activity {
+            kind,
+            id: None,
+            actor,
+            object: None,
+            target: None,
+            content: None,
+            published: None,
+            to: None,
+            cc: None,
+        }
+    }
+
+    /// The first `object`. Most activities have exactly one.
+    pub fn object(&self) -> Option<&IriOrObject<AnyObject>> {
+        self.object.as_ref().and_then(|o| o.first())
+    }
+}
+
+impl Identified for Activity {
+    fn id(&self) -> Option<&Url> {
+        self.id.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkKind {
+    Hashtag,
+    Link,
+    Mention,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    #[serde(rename = "type")]
+    pub kind: LinkKind,
+
+    pub href: Url,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rel: Option<OneOrMany<String>>,
+}
+
+impl Identified for Link {
+    fn id(&self) -> Option<&Url> {
+        Some(&self.href)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropertyValueKind {
+    PropertyValue,
+}
+
+/// Name-value pair defined in schema.org, used for profile fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PropertyValue {
+    #[serde(rename = "type")]
+    pub kind: PropertyValueKind,
+
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectionKind {
+    Collection,
+    OrderedCollection,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collection {
+    #[serde(rename = "type")]
+    pub kind: CollectionKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first: Option<IriOrObject<CollectionPage>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last: Option<IriOrObject<CollectionPage>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<OneOrMany<IriOrObject<AnyObject>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ordered_items: Option<OneOrMany<IriOrObject<AnyObject>>>,
+}
+
+impl Identified for Collection {
+    fn id(&self) -> Option<&Url> {
+        self.id.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectionPageKind {
+    CollectionPage,
+    OrderedCollectionPage,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionPage {
+    #[serde(rename = "type")]
+    pub kind: CollectionPageKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<IriOrObject<CollectionPage>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev: Option<IriOrObject<CollectionPage>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<OneOrMany<IriOrObject<AnyObject>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ordered_items: Option<OneOrMany<IriOrObject<AnyObject>>>,
+}
+
+impl Identified for CollectionPage {
+    fn id(&self) -> Option<&Url> {
+        self.id.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Activity, ActivityKind, Actor, AnyObject, IriOrObject, ObjectKind, OneOrMany};
+
+    use serde_json::json;
+
+    #[test]
+    fn one_or_many_accepts_both() {
+        let one: OneOrMany<String> = serde_json::from_value(json!("a")).unwrap();
+        let many: OneOrMany<String> = serde_json::from_value(json!(["a", "b"])).unwrap();
+        assert_eq!(one.into_vec(), vec!["a"]);
+        assert_eq!(many.into_vec(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn activity_accepts_iri_and_embedded_object() {
+        let follow: Activity = serde_json::from_value(json!({
+            "id": "https://remote.example/activities/1",
+            "type": "Follow",
+            "actor": "https://remote.example/users/alice",
+            "object": "https://local.example/users/bob",
+        }))
+        .unwrap();
+        assert_eq!(follow.kind, ActivityKind::Follow);
+        assert_eq!(
+            follow.actor.id().map(|u| u.as_str()),
+            Some("https://remote.example/users/alice")
+        );
+
+        let undo: Activity = serde_json::from_value(json!({
+            "type": "Undo",
+            "actor": "https://remote.example/users/alice",
+            "object": follow,
+        }))
+        .unwrap();
+        let Some(IriOrObject::Object(object)) = undo.object() else {
+            panic!("object must be embedded");
+        };
+        assert!(matches!(**object, AnyObject::Activity(ref a) if a.kind == ActivityKind::Follow));
+    }
+
+    #[test]
+    fn actor_accepts_single_alias() {
+        let actor: Actor = serde_json::from_value(json!({
+            "id": "https://remote.example/users/alice",
+            "type": "Person",
+            "inbox": "https://remote.example/users/alice/inbox",
+            "alsoKnownAs": "https://old.example/users/alice",
+        }))
+        .unwrap();
+        assert!(actor.is_also_known_as("https://old.example/users/alice"));
+        assert!(!actor.is_also_known_as("https://remote.example/users/alice"));
+    }
+
+    #[test]
+    fn object_accepts_unknown_type() {
+        let object: AnyObject = serde_json::from_value(json!({
+            "id": "https://remote.example/emojis/1",
+            "type": "Emoji",
+            "to": "https://www.w3.org/ns/activitystreams#Public",
+        }))
+        .unwrap();
+        let AnyObject::Object(object) = object else {
+            panic!("must be parsed as Object");
+        };
+        assert_eq!(object.kind, ObjectKind::Unknown);
+        assert_eq!(object.addressees().count(), 1);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::ap::acct_origin_of;
+
 use std::{net::SocketAddr, path::Path};
 
 use anyhow::{ensure, Result};
@@ -60,6 +62,11 @@ pub struct ConfigServer {
 
     /// Incoming server port.
     pub port: Option<u16>,
+
+    /// Requires HTTP Signature on fetches of actors, notes and collections.
+    /// Unsigned requests to actors get only their public keys.
+    #[serde(default)]
+    pub authorized_fetch: bool,
 }
 
 impl Default for ConfigServer {
@@ -69,6 +76,7 @@ impl Default for ConfigServer {
             schema: "http".into(),
             domain: "localhost".into(),
             port: None,
+            authorized_fetch: false,
         }
     }
 }
@@ -132,6 +140,10 @@ pub struct ConfigUser {
 
     /// Banned (or reserved) usernames.
     pub banned_usernames: Vec<String>,
+
+    /// Hides items of followers and following collections and exposes only the number.
+    #[serde(default)]
+    pub hide_follow_collections: bool,
 }
 
 impl Default for ConfigUser {
@@ -140,6 +152,7 @@ impl Default for ConfigUser {
             registration: UserRegistration::Closed,
             username_max_length: 32,
             banned_usernames: vec![],
+            hide_follow_collections: false,
         }
     }
 }
@@ -180,12 +193,7 @@ impl ConfigCached {
     /// Constructs acct domain string, which may contain port part.
     /// Returned value will be like `example.com` or `localhost:3000`.
     pub fn acct_origin(&self) -> String {
-        let host = self.server_base_url.host_str().expect("invalid base URL");
-        if let Some(port) = self.server_base_url.port() {
-            format!("{host}:{port}")
-        } else {
-            host.to_string()
-        }
+        acct_origin_of(&self.server_base_url).expect("invalid base URL")
     }
 }
 
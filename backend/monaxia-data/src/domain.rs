@@ -0,0 +1,96 @@
+use std::str::FromStr;
+
+use thiserror::Error as ThisError;
+use time::OffsetDateTime;
+
+/// Moderation policy applied to a remote domain and its subdomains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DomainPolicy {
+    /// No restriction.
+    #[default]
+    None,
+
+    /// Follows from the domain are held as pending instead of being accepted.
+    Silence,
+
+    /// All communication with the domain is refused.
+    Suspend,
+
+    /// Media of the domain such as avatars are not stored.
+    RejectMedia,
+}
+
+#[derive(Debug, Clone, ThisError)]
+#[error("unknown domain policy: {0}")]
+pub struct DomainPolicyError(String);
+
+impl DomainPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DomainPolicy::None => "none",
+            DomainPolicy::Silence => "silence",
+            DomainPolicy::Suspend => "suspend",
+            DomainPolicy::RejectMedia => "reject-media",
+        }
+    }
+}
+
+impl FromStr for DomainPolicy {
+    type Err = DomainPolicyError;
+
+    fn from_str(s: &str) -> Result<DomainPolicy, DomainPolicyError> {
+        match s {
+            "none" => Ok(DomainPolicy::None),
+            "silence" => Ok(DomainPolicy::Silence),
+            "suspend" => Ok(DomainPolicy::Suspend),
+            "reject-media" => Ok(DomainPolicy::RejectMedia),
+            otherwise => Err(DomainPolicyError(otherwise.to_string())),
+        }
+    }
+}
+
+/// Domain with moderation policy other than none.
+#[derive(Debug, Clone)]
+pub struct ModeratedDomain {
+    pub domain: String,
+    pub policy: DomainPolicy,
+    pub reason: Option<String>,
+    pub recognized_at: OffsetDateTime,
+}
+
+/// Lists the domain itself and its parent domains, from the most specific one.
+/// Port part is dropped for parent domains, so `sub.example.com:3000` is covered
+/// by `sub.example.com` and `example.com`.
+pub fn covering_domains(domain: &str) -> Vec<&str> {
+    let mut domains = vec![domain];
+    let mut host = domain.split_once(':').map_or(domain, |(host, _)| host);
+    if host != domain {
+        domains.push(host);
+    }
+    while let Some((_, parent)) = host.split_once('.') {
+        if !parent.contains('.') {
+            break;
+        }
+        domains.push(parent);
+        host = parent;
+    }
+    domains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::covering_domains;
+
+    #[test]
+    fn covering_domains_lists_parents() {
+        assert_eq!(
+            covering_domains("a.b.example.com"),
+            vec!["a.b.example.com", "b.example.com", "example.com"]
+        );
+        assert_eq!(
+            covering_domains("example.com:3000"),
+            vec!["example.com:3000", "example.com"]
+        );
+        assert_eq!(covering_domains("localhost"), vec!["localhost"]);
+    }
+}
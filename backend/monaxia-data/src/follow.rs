@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+use thiserror::Error as ThisError;
+
+/// State of follow relationship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowState {
+    /// Follow request is sent but not accepted yet.
+    Pending,
+
+    /// Follow request is accepted.
+    Accepted,
+}
+
+#[derive(Debug, Clone, ThisError)]
+#[error("unknown follow state: {0}")]
+pub struct FollowStateError(String);
+
+impl FollowState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FollowState::Pending => "pending",
+            FollowState::Accepted => "accepted",
+        }
+    }
+}
+
+impl FromStr for FollowState {
+    type Err = FollowStateError;
+
+    fn from_str(s: &str) -> Result<FollowState, FollowStateError> {
+        match s {
+            "pending" => Ok(FollowState::Pending),
+            "accepted" => Ok(FollowState::Accepted),
+            otherwise => Err(FollowStateError(otherwise.to_string())),
+        }
+    }
+}
+
+/// Follow relationship between users.
+#[derive(Debug, Clone)]
+pub struct Follow {
+    pub id: String,
+    pub follower_id: String,
+    pub followee_id: String,
+    pub state: FollowState,
+
+    /// ID of the Follow activity which established this relationship.
+    pub activity_id: String,
+}
+
+#[derive(Debug)]
+pub struct FollowRegistration {
+    pub follower_id: String,
+    pub followee_id: String,
+    pub state: FollowState,
+    pub activity_id: String,
+}
+
+/// Counterpart user in follow relationships.
+#[derive(Debug, Clone)]
+pub struct FollowRelated {
+    /// ID of the relationship, used for pagination.
+    pub follow_id: String,
+    pub user_id: String,
+
+    /// Actor URI for remote users. Local users have `None`.
+    pub uri: Option<String>,
+}
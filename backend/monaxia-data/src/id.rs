@@ -10,6 +10,9 @@ const ORDER58_CHARS: [char; 58] = [
     'z',
 ];
 
+/// Order58 representation of zero, which precedes any other IDs.
+pub const ORDER58_ZERO: &str = "1";
+
 #[derive(Debug, Clone, Copy, ThisError)]
 pub enum Order58Error {
     #[error("invalid digit detected")]
@@ -61,7 +64,7 @@ pub fn cmp_mstdn_id(lhs: &str, rhs: &str) -> Ordering {
 
 #[cfg(test)]
 mod tests {
-    use super::{cmp_mstdn_id, from_order58, now_order58, to_order58};
+    use super::{cmp_mstdn_id, from_order58, now_order58, to_order58, ORDER58_ZERO};
 
     use std::{cmp::Ordering, thread::sleep, time::Duration};
 
@@ -79,6 +82,11 @@ mod tests {
         assert_eq!(decoded.unwrap(), base_uuid);
     }
 
+    #[test]
+    fn order58_zero_is_nil() {
+        assert_eq!(to_order58(Uuid::nil()), ORDER58_ZERO);
+    }
+
     #[test]
     fn mstdn_cmp_works() {
         assert_eq!(cmp_mstdn_id("1", "2"), Ordering::Less);
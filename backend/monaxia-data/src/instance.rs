@@ -0,0 +1,15 @@
+use rsa::RsaPrivateKey;
+use time::OffsetDateTime;
+
+/// `Application` actor representing the instance itself.
+/// Used for signing requests made without any local user context.
+#[derive(Debug, Clone)]
+pub struct InstanceActor {
+    pub public_key: String,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct InstanceActorRegistration {
+    pub private_key: RsaPrivateKey,
+}
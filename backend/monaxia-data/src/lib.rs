@@ -1,5 +1,12 @@
+pub mod activity;
 pub mod ap;
 pub mod config;
+pub mod domain;
+pub mod follow;
 pub mod id;
+pub mod instance;
 pub mod migration;
+pub mod note;
+pub mod pagination;
+pub mod reaction;
 pub mod user;
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use thiserror::Error as ThisError;
+use time::OffsetDateTime;
+
+/// Note posted by a local user.
+#[derive(Debug, Clone)]
+pub struct Note {
+    pub id: String,
+    pub user_id: String,
+
+    /// HTML content.
+    pub content: String,
+    pub published: OffsetDateTime,
+    pub in_reply_to: Option<String>,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+}
+
+/// Trace of a deleted note.
+#[derive(Debug, Clone)]
+pub struct NoteTombstone {
+    pub id: String,
+    pub deleted: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct NoteRegistration {
+    /// Order58 ID, which is also used for the note URL.
+    pub id: String,
+    pub user_id: String,
+    pub content: String,
+    pub published: OffsetDateTime,
+    pub in_reply_to: Option<String>,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+}
+
+/// Audience of a new note, which determines its addressing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteVisibility {
+    /// Addressed to the public and shown in public timelines.
+    Public,
+
+    /// Addressed to the public but not shown in public timelines.
+    Unlisted,
+
+    /// Addressed to followers only.
+    Followers,
+}
+
+#[derive(Debug, Clone, ThisError)]
+#[error("unknown visibility: {0}")]
+pub struct NoteVisibilityError(String);
+
+impl FromStr for NoteVisibility {
+    type Err = NoteVisibilityError;
+
+    fn from_str(s: &str) -> Result<NoteVisibility, NoteVisibilityError> {
+        match s {
+            "public" => Ok(NoteVisibility::Public),
+            "unlisted" => Ok(NoteVisibility::Unlisted),
+            "followers" => Ok(NoteVisibility::Followers),
+            otherwise => Err(NoteVisibilityError(otherwise.to_string())),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+/// Pagination by order58 IDs.
+/// Items are always returned in descending order.
+#[derive(Debug, Clone, Default)]
+pub struct Pagination {
+    /// Returns items older than this ID.
+    pub max_id: Option<String>,
+
+    /// Returns items newer than this ID.
+    /// If `max_id` is not specified, items right after this ID will be returned.
+    pub min_id: Option<String>,
+
+    /// Maximum count of items.
+    pub limit: usize,
+}
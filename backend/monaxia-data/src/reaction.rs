@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+use thiserror::Error as ThisError;
+use time::OffsetDateTime;
+
+/// Kind of reactions to objects, named after the activity type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionKind {
+    /// Like (favourite).
+    Like,
+
+    /// Announce (boost).
+    Announce,
+}
+
+#[derive(Debug, Clone, ThisError)]
+#[error("unknown reaction kind: {0}")]
+pub struct ReactionKindError(String);
+
+impl ReactionKind {
+    /// Returns the activity type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionKind::Like => "Like",
+            ReactionKind::Announce => "Announce",
+        }
+    }
+}
+
+impl FromStr for ReactionKind {
+    type Err = ReactionKindError;
+
+    fn from_str(s: &str) -> Result<ReactionKind, ReactionKindError> {
+        match s {
+            "Like" => Ok(ReactionKind::Like),
+            "Announce" => Ok(ReactionKind::Announce),
+            otherwise => Err(ReactionKindError(otherwise.to_string())),
+        }
+    }
+}
+
+/// Like or Announce of an object by a user.
+#[derive(Debug, Clone)]
+pub struct Reaction {
+    pub id: String,
+    pub kind: ReactionKind,
+    pub user_id: String,
+    pub object_uri: String,
+
+    /// ID of the Like or Announce activity.
+    pub activity_id: String,
+
+    /// Actor URI of the author of the object, recorded for reactions by local users.
+    pub author_uri: Option<String>,
+
+    /// Inbox of the author of the object if remote.
+    pub author_inbox: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct ReactionRegistration {
+    pub kind: ReactionKind,
+    pub user_id: String,
+    pub object_uri: String,
+    pub activity_id: String,
+    pub author_uri: Option<String>,
+    pub author_inbox: Option<String>,
+}
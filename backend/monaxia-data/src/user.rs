@@ -3,7 +3,9 @@ use std::ops::RangeInclusive;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde::{Deserialize, Serialize};
 use thiserror::Error as ThisError;
+use time::OffsetDateTime;
 
 static RE_USERNAME: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"^[A-Za-z0-9_]+$"#).expect("invalid regex"));
@@ -28,6 +30,13 @@ pub enum UsernameError {
 #[derive(Debug)]
 pub struct RemoteUserRegistration {
     pub username: String,
+    pub uri: String,
+    pub key_id: String,
+    pub inbox: String,
+    pub shared_inbox: Option<String>,
+    pub display_name: Option<String>,
+    pub summary: Option<String>,
+    pub avatar_url: Option<String>,
     pub public_key: RsaPublicKey,
 }
 
@@ -37,12 +46,64 @@ pub struct LocalUserRegistration {
     pub private_key: RsaPrivateKey,
 }
 
+/// New key pair of a local user, replacing the current one.
+#[derive(Debug)]
+pub struct LocalUserKeyRotation {
+    pub private_key: RsaPrivateKey,
+}
+
 #[derive(Debug, Clone)]
 pub struct LocalUser {
     pub id: String,
     pub id_seq: String,
     pub username: String,
     pub public_key: String,
+    pub profile: LocalUserProfile,
+    pub created_at: OffsetDateTime,
+}
+
+/// Editable profile of a local user.
+/// `summary` and values of `fields` are HTML.
+#[derive(Debug, Clone, Default)]
+pub struct LocalUserProfile {
+    pub display_name: Option<String>,
+    pub summary: Option<String>,
+    pub avatar_url: Option<String>,
+    pub header_url: Option<String>,
+    pub manually_approves_followers: bool,
+    pub fields: Vec<ProfileField>,
+
+    /// Actor URIs of other accounts of the user, exposed as `alsoKnownAs`.
+    pub also_known_as: Vec<String>,
+
+    /// Actor URI of the account the user has moved to.
+    pub moved_to: Option<String>,
+}
+
+/// Name-value pair shown in the profile, exposed as PropertyValue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileField {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteUser {
+    pub id: String,
+    pub id_seq: String,
+    pub username: String,
+    pub domain: String,
+    pub uri: String,
+    pub key_id: String,
+    pub inbox: Option<String>,
+    pub shared_inbox: Option<String>,
+    pub display_name: Option<String>,
+    pub summary: Option<String>,
+    pub avatar_url: Option<String>,
+    pub public_key: String,
+
+    /// Last time the actor was fetched. `None` if never fetched.
+    pub fetched_at: Option<OffsetDateTime>,
 }
 
 /// Validates username format.
@@ -0,0 +1,131 @@
+use super::schema::{Activity, ActivityDef, ActivityInsertion};
+
+use sea_query::{Expr, Func, Order, PostgresQueryBuilder as QueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{PgConnection as Connection, Result as SqlxResult};
+
+pub async fn register_activity(
+    conn: &mut Connection,
+    insertion: ActivityInsertion,
+) -> SqlxResult<()> {
+    let (query, values) = Query::insert()
+        .into_table(ActivityDef::Table)
+        .columns([
+            ActivityDef::Id,
+            ActivityDef::UserId,
+            ActivityDef::ActivityType,
+            ActivityDef::Body,
+            ActivityDef::Public,
+        ])
+        .values([
+            insertion.id.into(),
+            insertion.user_id.into(),
+            insertion.activity_type.into(),
+            insertion.body.into(),
+            insertion.public.into(),
+        ])
+        .expect("failed to encode")
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn find_activity_by_id(
+    conn: &mut Connection,
+    activity_id: &str,
+) -> SqlxResult<Option<Activity>> {
+    let (query, values) = Query::select()
+        .columns([
+            ActivityDef::Id,
+            ActivityDef::UserId,
+            ActivityDef::ActivityType,
+            ActivityDef::Body,
+            ActivityDef::Public,
+            ActivityDef::PublishedAt,
+        ])
+        .from(ActivityDef::Table)
+        .and_where(Expr::col(ActivityDef::Id).eq(activity_id))
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+pub async fn count_public_activities(conn: &mut Connection, user_id: &str) -> SqlxResult<usize> {
+    let (query, values) = Query::select()
+        .expr(Func::count(Expr::col(ActivityDef::Id)))
+        .from(ActivityDef::Table)
+        .and_where(Expr::col(ActivityDef::UserId).eq(user_id))
+        .and_where(Expr::col(ActivityDef::Public).eq(true))
+        .build_sqlx(QueryBuilder);
+    let (value,): (i64,) = sqlx::query_as_with(&query, values)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(value as usize)
+}
+
+/// Fetches public activities of the user in descending order of ID.
+/// If only `min_id` is specified, returns activities right after it.
+pub async fn fetch_public_activities(
+    conn: &mut Connection,
+    user_id: &str,
+    max_id: Option<&str>,
+    min_id: Option<&str>,
+    limit: u64,
+) -> SqlxResult<Vec<Activity>> {
+    let ascending = min_id.is_some() && max_id.is_none();
+
+    let mut select = Query::select();
+    select
+        .columns([
+            ActivityDef::Id,
+            ActivityDef::UserId,
+            ActivityDef::ActivityType,
+            ActivityDef::Body,
+            ActivityDef::Public,
+            ActivityDef::PublishedAt,
+        ])
+        .from(ActivityDef::Table)
+        .and_where(Expr::col(ActivityDef::UserId).eq(user_id))
+        .and_where(Expr::col(ActivityDef::Public).eq(true));
+    if let Some(max_id) = max_id {
+        select.and_where(Expr::col(ActivityDef::Id).lt(max_id));
+    }
+    if let Some(min_id) = min_id {
+        select.and_where(Expr::col(ActivityDef::Id).gt(min_id));
+    }
+    let (query, values) = select
+        .order_by(
+            ActivityDef::Id,
+            if ascending { Order::Asc } else { Order::Desc },
+        )
+        .limit(limit)
+        .build_sqlx(QueryBuilder);
+
+    let mut rows: Vec<Activity> = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    if ascending {
+        rows.reverse();
+    }
+    Ok(rows)
+}
+
+/// Deletes Create activities of the object, so that outbox no longer exposes it.
+pub async fn delete_create_activities(conn: &mut Connection, object_uri: &str) -> SqlxResult<()> {
+    let (query, values) = Query::delete()
+        .from_table(ActivityDef::Table)
+        .and_where(Expr::col(ActivityDef::ActivityType).eq("Create"))
+        .and_where(Expr::cust_with_values(
+            r#""body" -> 'object' ->> 'id' = $1"#,
+            [object_uri],
+        ))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use sea_query::Iden;
+use serde_json::Value as JsonValue;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Iden)]
+pub enum ActivityDef {
+    #[iden = "activities"]
+    Table,
+    Id,
+    UserId,
+    ActivityType,
+    Body,
+    Public,
+    PublishedAt,
+}
+
+#[derive(Debug)]
+pub struct ActivityInsertion {
+    pub id: String,
+    pub user_id: String,
+    pub activity_type: String,
+    pub body: JsonValue,
+    pub public: bool,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Activity {
+    pub id: String,
+    pub user_id: String,
+    pub activity_type: String,
+    pub body: JsonValue,
+    pub public: bool,
+    pub published_at: OffsetDateTime,
+}
@@ -1,9 +1,18 @@
-use super::schema::DomainDef;
+use super::schema::{Domain, DomainDef};
 
-use sea_query::{OnConflict, PostgresQueryBuilder as QueryBuilder, Query};
+use sea_query::{Expr, OnConflict, Order, PostgresQueryBuilder as QueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
 use sqlx::{PgConnection as Connection, Result as SqlxResult};
 
+const DOMAIN_COLUMNS: [DomainDef; 4] = [
+    DomainDef::Domain,
+    DomainDef::RecognizedAt,
+    DomainDef::Policy,
+    DomainDef::PolicyReason,
+];
+
+const POLICY_NONE: &str = "none";
+
 pub async fn register_domain(conn: &mut Connection, domain: &str) -> SqlxResult<bool> {
     let (query, values) = Query::insert()
         .into_table(DomainDef::Table)
@@ -20,3 +29,63 @@ pub async fn register_domain(conn: &mut Connection, domain: &str) -> SqlxResult<
     let result = sqlx::query_with(&query, values).execute(&mut *conn).await?;
     Ok(result.rows_affected() == 1)
 }
+
+/// Sets the policy of the domain, registering the domain if unknown.
+pub async fn upsert_domain_policy(
+    conn: &mut Connection,
+    domain: &str,
+    policy: &str,
+    reason: Option<&str>,
+) -> SqlxResult<()> {
+    let (query, values) = Query::insert()
+        .into_table(DomainDef::Table)
+        .columns([
+            DomainDef::Domain,
+            DomainDef::Policy,
+            DomainDef::PolicyReason,
+        ])
+        .values([domain.into(), policy.into(), reason.into()])
+        .expect("failed to encode")
+        .on_conflict(
+            OnConflict::column(DomainDef::Domain)
+                .update_columns([DomainDef::Policy, DomainDef::PolicyReason])
+                .to_owned(),
+        )
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+/// Fetches domains which have any policy among the specified ones.
+pub async fn fetch_moderated_domains_in(
+    conn: &mut Connection,
+    domains: &[&str],
+) -> SqlxResult<Vec<Domain>> {
+    let (query, values) = Query::select()
+        .columns(DOMAIN_COLUMNS)
+        .from(DomainDef::Table)
+        .and_where(Expr::col(DomainDef::Domain).is_in(domains.iter().copied()))
+        .and_where(Expr::col(DomainDef::Policy).ne(POLICY_NONE))
+        .build_sqlx(QueryBuilder);
+
+    let rows = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows)
+}
+
+/// Fetches all domains which have any policy, in alphabetical order.
+pub async fn fetch_moderated_domains(conn: &mut Connection) -> SqlxResult<Vec<Domain>> {
+    let (query, values) = Query::select()
+        .columns(DOMAIN_COLUMNS)
+        .from(DomainDef::Table)
+        .and_where(Expr::col(DomainDef::Policy).ne(POLICY_NONE))
+        .order_by(DomainDef::Domain, Order::Asc)
+        .build_sqlx(QueryBuilder);
+
+    let rows = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows)
+}
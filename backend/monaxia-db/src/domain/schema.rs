@@ -1,4 +1,6 @@
 use sea_query::Iden;
+use sqlx::FromRow;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone, Copy, Iden)]
 pub enum DomainDef {
@@ -6,4 +8,14 @@ pub enum DomainDef {
     Table,
     Domain,
     RecognizedAt,
+    Policy,
+    PolicyReason,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Domain {
+    pub domain: String,
+    pub recognized_at: OffsetDateTime,
+    pub policy: String,
+    pub policy_reason: Option<String>,
 }
@@ -0,0 +1,311 @@
+use super::schema::{Follow, FollowDef, FollowInsertion, FollowRelated};
+use crate::user::schema::{LocalUserDef, UserDef};
+
+use sea_query::{
+    Alias, Expr, Func, JoinType, OnConflict, Order, PostgresQueryBuilder as QueryBuilder, Query,
+    SimpleExpr,
+};
+use sea_query_binder::SqlxBinder;
+use sqlx::{PgConnection as Connection, Result as SqlxResult};
+
+const FOLLOW_COLUMNS: [FollowDef; 6] = [
+    FollowDef::Id,
+    FollowDef::FollowerId,
+    FollowDef::FolloweeId,
+    FollowDef::State,
+    FollowDef::ActivityId,
+    FollowDef::CreatedAt,
+];
+
+/// Inserts a follow relationship, or overwrites state and activity ID of existing one.
+/// Returns the ID of the relationship.
+pub async fn upsert_follow(
+    conn: &mut Connection,
+    insertion: FollowInsertion,
+) -> SqlxResult<String> {
+    let (query, values) = Query::insert()
+        .into_table(FollowDef::Table)
+        .columns([
+            FollowDef::Id,
+            FollowDef::FollowerId,
+            FollowDef::FolloweeId,
+            FollowDef::State,
+            FollowDef::ActivityId,
+        ])
+        .values([
+            insertion.id.into(),
+            insertion.follower_id.into(),
+            insertion.followee_id.into(),
+            insertion.state.into(),
+            insertion.activity_id.into(),
+        ])
+        .expect("failed to encode")
+        .on_conflict(
+            OnConflict::columns([FollowDef::FollowerId, FollowDef::FolloweeId])
+                .update_columns([FollowDef::State, FollowDef::ActivityId])
+                .to_owned(),
+        )
+        .returning_col(FollowDef::Id)
+        .build_sqlx(QueryBuilder);
+
+    let (id,): (String,) = sqlx::query_as_with(&query, values)
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(id)
+}
+
+pub async fn find_follow_by_pair(
+    conn: &mut Connection,
+    follower_id: &str,
+    followee_id: &str,
+) -> SqlxResult<Option<Follow>> {
+    find_follow(
+        conn,
+        Expr::col(FollowDef::FollowerId)
+            .eq(follower_id)
+            .and(Expr::col(FollowDef::FolloweeId).eq(followee_id)),
+    )
+    .await
+}
+
+pub async fn find_follow_by_activity_id(
+    conn: &mut Connection,
+    activity_id: &str,
+) -> SqlxResult<Option<Follow>> {
+    find_follow(conn, Expr::col(FollowDef::ActivityId).eq(activity_id)).await
+}
+
+/// Fetches distinct inboxes of accepted remote followers.
+/// Shared inboxes are preferred if available.
+pub async fn fetch_follower_inboxes(
+    conn: &mut Connection,
+    user_id: &str,
+) -> SqlxResult<Vec<String>> {
+    let (query, values) = Query::select()
+        .distinct()
+        .expr(Func::coalesce([
+            Expr::col((UserDef::Table, UserDef::SharedInbox)).into(),
+            Expr::col((UserDef::Table, UserDef::Inbox)).into(),
+        ]))
+        .from(FollowDef::Table)
+        .join(
+            JoinType::InnerJoin,
+            UserDef::Table,
+            Expr::col((FollowDef::Table, FollowDef::FollowerId))
+                .equals((UserDef::Table, UserDef::Id)),
+        )
+        .and_where(Expr::col((FollowDef::Table, FollowDef::FolloweeId)).eq(user_id))
+        .and_where(Expr::col((FollowDef::Table, FollowDef::State)).eq("accepted"))
+        .and_where(Expr::col((UserDef::Table, UserDef::Inbox)).is_not_null())
+        .build_sqlx(QueryBuilder);
+
+    let rows: Vec<(String,)> = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows.into_iter().map(|(inbox,)| inbox).collect())
+}
+
+/// Fetches IDs of local users who follow or have requested to follow the user.
+pub async fn fetch_local_follower_ids(
+    conn: &mut Connection,
+    user_id: &str,
+) -> SqlxResult<Vec<String>> {
+    let (query, values) = Query::select()
+        .column((FollowDef::Table, FollowDef::FollowerId))
+        .from(FollowDef::Table)
+        .join(
+            JoinType::InnerJoin,
+            LocalUserDef::Table,
+            Expr::col((FollowDef::Table, FollowDef::FollowerId))
+                .equals((LocalUserDef::Table, LocalUserDef::UserId)),
+        )
+        .and_where(Expr::col((FollowDef::Table, FollowDef::FolloweeId)).eq(user_id))
+        .build_sqlx(QueryBuilder);
+
+    let rows: Vec<(String,)> = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+/// Fetches followers who have requested to follow the user and are not accepted yet,
+/// in ascending order of follow ID.
+pub async fn fetch_pending_followers(
+    conn: &mut Connection,
+    user_id: &str,
+) -> SqlxResult<Vec<FollowRelated>> {
+    let (query, values) = Query::select()
+        .column((FollowDef::Table, FollowDef::Id))
+        .expr_as(
+            Expr::col((UserDef::Table, UserDef::Id)),
+            Alias::new("user_id"),
+        )
+        .column((UserDef::Table, UserDef::Uri))
+        .from(FollowDef::Table)
+        .join(
+            JoinType::InnerJoin,
+            UserDef::Table,
+            Expr::col((FollowDef::Table, FollowDef::FollowerId))
+                .equals((UserDef::Table, UserDef::Id)),
+        )
+        .and_where(Expr::col((FollowDef::Table, FollowDef::FolloweeId)).eq(user_id))
+        .and_where(Expr::col((FollowDef::Table, FollowDef::State)).eq("pending"))
+        .order_by((FollowDef::Table, FollowDef::Id), Order::Asc)
+        .build_sqlx(QueryBuilder);
+
+    let rows = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows)
+}
+
+pub async fn update_follow_state(
+    conn: &mut Connection,
+    follow_id: &str,
+    state: &str,
+) -> SqlxResult<()> {
+    let (query, values) = Query::update()
+        .table(FollowDef::Table)
+        .value(FollowDef::State, state)
+        .and_where(Expr::col(FollowDef::Id).eq(follow_id))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn delete_follow(conn: &mut Connection, follow_id: &str) -> SqlxResult<()> {
+    let (query, values) = Query::delete()
+        .from_table(FollowDef::Table)
+        .and_where(Expr::col(FollowDef::Id).eq(follow_id))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn count_followers(conn: &mut Connection, user_id: &str) -> SqlxResult<usize> {
+    count_accepted_follows(conn, FollowDef::FolloweeId, user_id).await
+}
+
+pub async fn count_followees(conn: &mut Connection, user_id: &str) -> SqlxResult<usize> {
+    count_accepted_follows(conn, FollowDef::FollowerId, user_id).await
+}
+
+/// Fetches followers of the user in descending order of follow ID.
+/// If only `min_id` is specified, returns followers right after it.
+pub async fn fetch_followers(
+    conn: &mut Connection,
+    user_id: &str,
+    max_id: Option<&str>,
+    min_id: Option<&str>,
+    limit: u64,
+) -> SqlxResult<Vec<FollowRelated>> {
+    fetch_accepted_follows(
+        conn,
+        (FollowDef::FolloweeId, FollowDef::FollowerId),
+        user_id,
+        (max_id, min_id),
+        limit,
+    )
+    .await
+}
+
+/// Fetches followees of the user in descending order of follow ID.
+/// If only `min_id` is specified, returns followees right after it.
+pub async fn fetch_followees(
+    conn: &mut Connection,
+    user_id: &str,
+    max_id: Option<&str>,
+    min_id: Option<&str>,
+    limit: u64,
+) -> SqlxResult<Vec<FollowRelated>> {
+    fetch_accepted_follows(
+        conn,
+        (FollowDef::FollowerId, FollowDef::FolloweeId),
+        user_id,
+        (max_id, min_id),
+        limit,
+    )
+    .await
+}
+
+async fn find_follow(conn: &mut Connection, condition: SimpleExpr) -> SqlxResult<Option<Follow>> {
+    let (query, values) = Query::select()
+        .columns(FOLLOW_COLUMNS)
+        .from(FollowDef::Table)
+        .and_where(condition)
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+async fn count_accepted_follows(
+    conn: &mut Connection,
+    target_column: FollowDef,
+    user_id: &str,
+) -> SqlxResult<usize> {
+    let (query, values) = Query::select()
+        .expr(Func::count(Expr::col(FollowDef::Id)))
+        .from(FollowDef::Table)
+        .and_where(Expr::col(target_column).eq(user_id))
+        .and_where(Expr::col(FollowDef::State).eq("accepted"))
+        .build_sqlx(QueryBuilder);
+    let (value,): (i64,) = sqlx::query_as_with(&query, values)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(value as usize)
+}
+
+/// Fetches counterparts of accepted follows whose `target_column` is the user.
+async fn fetch_accepted_follows(
+    conn: &mut Connection,
+    (target_column, related_column): (FollowDef, FollowDef),
+    user_id: &str,
+    (max_id, min_id): (Option<&str>, Option<&str>),
+    limit: u64,
+) -> SqlxResult<Vec<FollowRelated>> {
+    let ascending = min_id.is_some() && max_id.is_none();
+
+    let mut select = Query::select();
+    select
+        .column((FollowDef::Table, FollowDef::Id))
+        .expr_as(
+            Expr::col((UserDef::Table, UserDef::Id)),
+            Alias::new("user_id"),
+        )
+        .column((UserDef::Table, UserDef::Uri))
+        .from(FollowDef::Table)
+        .join(
+            JoinType::InnerJoin,
+            UserDef::Table,
+            Expr::col((FollowDef::Table, related_column)).equals((UserDef::Table, UserDef::Id)),
+        )
+        .and_where(Expr::col((FollowDef::Table, target_column)).eq(user_id))
+        .and_where(Expr::col((FollowDef::Table, FollowDef::State)).eq("accepted"));
+    if let Some(max_id) = max_id {
+        select.and_where(Expr::col((FollowDef::Table, FollowDef::Id)).lt(max_id));
+    }
+    if let Some(min_id) = min_id {
+        select.and_where(Expr::col((FollowDef::Table, FollowDef::Id)).gt(min_id));
+    }
+    let (query, values) = select
+        .order_by(
+            (FollowDef::Table, FollowDef::Id),
+            if ascending { Order::Asc } else { Order::Desc },
+        )
+        .limit(limit)
+        .build_sqlx(QueryBuilder);
+
+    let mut rows: Vec<FollowRelated> = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    if ascending {
+        rows.reverse();
+    }
+    Ok(rows)
+}
@@ -0,0 +1,41 @@
+use sea_query::Iden;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Iden)]
+pub enum FollowDef {
+    #[iden = "follows"]
+    Table,
+    Id,
+    FollowerId,
+    FolloweeId,
+    State,
+    ActivityId,
+    CreatedAt,
+}
+
+#[derive(Debug)]
+pub struct FollowInsertion {
+    pub id: String,
+    pub follower_id: String,
+    pub followee_id: String,
+    pub state: String,
+    pub activity_id: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Follow {
+    pub id: String,
+    pub follower_id: String,
+    pub followee_id: String,
+    pub state: String,
+    pub activity_id: String,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct FollowRelated {
+    pub id: String,
+    pub user_id: String,
+    pub uri: Option<String>,
+}
@@ -0,0 +1,51 @@
+use super::schema::{InstanceActor, InstanceActorDef, InstanceActorInsertion};
+
+use sea_query::{OnConflict, PostgresQueryBuilder as QueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{PgConnection as Connection, Result as SqlxResult};
+
+pub async fn find_instance_actor(conn: &mut Connection) -> SqlxResult<Option<InstanceActor>> {
+    let (query, values) = Query::select()
+        .columns([InstanceActorDef::CreatedAt, InstanceActorDef::PublicKey])
+        .from(InstanceActorDef::Table)
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+pub async fn fetch_instance_private_key(conn: &mut Connection) -> SqlxResult<Option<String>> {
+    let (query, values) = Query::select()
+        .column(InstanceActorDef::PrivateKey)
+        .from(InstanceActorDef::Table)
+        .build_sqlx(QueryBuilder);
+
+    let row: Option<(String,)> = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row.map(|(pk,)| pk))
+}
+
+/// Registers the key pair of the instance actor.
+/// Returns false if it has already been registered.
+pub async fn register_instance_actor(
+    conn: &mut Connection,
+    insertion: InstanceActorInsertion<'_>,
+) -> SqlxResult<bool> {
+    let (query, values) = Query::insert()
+        .into_table(InstanceActorDef::Table)
+        .columns([InstanceActorDef::PublicKey, InstanceActorDef::PrivateKey])
+        .values([insertion.public_key.into(), insertion.private_key.into()])
+        .expect("failed to encode")
+        .on_conflict(
+            OnConflict::column(InstanceActorDef::Singleton)
+                .do_nothing()
+                .to_owned(),
+        )
+        .build_sqlx(QueryBuilder);
+
+    let result = sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,25 @@
+use sea_query::Iden;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Iden)]
+pub enum InstanceActorDef {
+    #[iden = "instance_actor"]
+    Table,
+    Singleton,
+    CreatedAt,
+    PublicKey,
+    PrivateKey,
+}
+
+#[derive(Debug)]
+pub struct InstanceActorInsertion<'a> {
+    pub public_key: String,
+    pub private_key: &'a str, // this struct does not clear
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct InstanceActor {
+    pub created_at: OffsetDateTime,
+    pub public_key: String,
+}
@@ -1,11 +1,31 @@
+pub mod activity {
+    pub mod action;
+    pub mod schema;
+}
 pub mod domain {
     pub mod action;
     pub mod schema;
 }
+pub mod follow {
+    pub mod action;
+    pub mod schema;
+}
+pub mod instance {
+    pub mod action;
+    pub mod schema;
+}
 pub mod migration {
     pub mod action;
     pub mod schema;
 }
+pub mod note {
+    pub mod action;
+    pub mod schema;
+}
+pub mod reaction {
+    pub mod action;
+    pub mod schema;
+}
 pub mod user {
     pub mod action;
     pub mod schema;
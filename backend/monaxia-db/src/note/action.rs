@@ -0,0 +1,111 @@
+use super::schema::{Note, NoteDef, NoteInsertion, NoteTombstone, NoteTombstoneDef};
+
+use sea_query::{Expr, OnConflict, PostgresQueryBuilder as QueryBuilder, Query, SimpleExpr};
+use sea_query_binder::SqlxBinder;
+use sqlx::{PgConnection as Connection, Result as SqlxResult};
+
+pub async fn register_note(conn: &mut Connection, insertion: NoteInsertion) -> SqlxResult<()> {
+    let (query, values) = Query::insert()
+        .into_table(NoteDef::Table)
+        .columns([
+            NoteDef::Id,
+            NoteDef::UserId,
+            NoteDef::Content,
+            NoteDef::PublishedAt,
+            NoteDef::InReplyTo,
+            NoteDef::To,
+            NoteDef::Cc,
+        ])
+        .values([
+            insertion.id.into(),
+            insertion.user_id.into(),
+            insertion.content.into(),
+            insertion.published_at.into(),
+            insertion.in_reply_to.into(),
+            insertion.to.into(),
+            insertion.cc.into(),
+        ])
+        .expect("failed to encode")
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn find_note_by_id(conn: &mut Connection, note_id: &str) -> SqlxResult<Option<Note>> {
+    let (query, values) = Query::select()
+        .columns([
+            NoteDef::Id,
+            NoteDef::UserId,
+            NoteDef::Content,
+            NoteDef::PublishedAt,
+            NoteDef::InReplyTo,
+            NoteDef::To,
+            NoteDef::Cc,
+        ])
+        .from(NoteDef::Table)
+        .and_where(Expr::col(NoteDef::Id).eq(note_id))
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+pub async fn find_tombstone_by_id(
+    conn: &mut Connection,
+    note_id: &str,
+) -> SqlxResult<Option<NoteTombstone>> {
+    let (query, values) = Query::select()
+        .columns([NoteTombstoneDef::Id, NoteTombstoneDef::DeletedAt])
+        .from(NoteTombstoneDef::Table)
+        .and_where(Expr::col(NoteTombstoneDef::Id).eq(note_id))
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+/// Replaces the note with a tombstone. Returns false if the note does not exist.
+pub async fn tombstone_note(conn: &mut Connection, note_id: &str) -> SqlxResult<bool> {
+    let deleted = tombstone_notes(conn, Expr::col(NoteDef::Id).eq(note_id)).await?;
+    Ok(deleted == 1)
+}
+
+/// Replaces all notes of the user with tombstones.
+pub async fn tombstone_user_notes(conn: &mut Connection, user_id: &str) -> SqlxResult<()> {
+    tombstone_notes(conn, Expr::col(NoteDef::UserId).eq(user_id)).await?;
+    Ok(())
+}
+
+/// Should be called in a transaction.
+async fn tombstone_notes(conn: &mut Connection, condition: SimpleExpr) -> SqlxResult<u64> {
+    let (query, values) = Query::insert()
+        .into_table(NoteTombstoneDef::Table)
+        .columns([NoteTombstoneDef::Id])
+        .select_from(
+            Query::select()
+                .column(NoteDef::Id)
+                .from(NoteDef::Table)
+                .and_where(condition.clone())
+                .to_owned(),
+        )
+        .expect("failed to encode")
+        .on_conflict(
+            OnConflict::column(NoteTombstoneDef::Id)
+                .do_nothing()
+                .to_owned(),
+        )
+        .build_sqlx(QueryBuilder);
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+
+    let (query, values) = Query::delete()
+        .from_table(NoteDef::Table)
+        .and_where(condition)
+        .build_sqlx(QueryBuilder);
+    let result = sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(result.rows_affected())
+}
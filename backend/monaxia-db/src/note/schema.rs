@@ -0,0 +1,53 @@
+use sea_query::Iden;
+use serde_json::Value as JsonValue;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Iden)]
+pub enum NoteDef {
+    #[iden = "notes"]
+    Table,
+    Id,
+    UserId,
+    Content,
+    PublishedAt,
+    InReplyTo,
+    To,
+    Cc,
+}
+
+#[derive(Debug, Clone, Copy, Iden)]
+pub enum NoteTombstoneDef {
+    #[iden = "note_tombstones"]
+    Table,
+    Id,
+    DeletedAt,
+}
+
+#[derive(Debug)]
+pub struct NoteInsertion {
+    pub id: String,
+    pub user_id: String,
+    pub content: String,
+    pub published_at: OffsetDateTime,
+    pub in_reply_to: Option<String>,
+    pub to: JsonValue,
+    pub cc: JsonValue,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Note {
+    pub id: String,
+    pub user_id: String,
+    pub content: String,
+    pub published_at: OffsetDateTime,
+    pub in_reply_to: Option<String>,
+    pub to: JsonValue,
+    pub cc: JsonValue,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct NoteTombstone {
+    pub id: String,
+    pub deleted_at: OffsetDateTime,
+}
@@ -0,0 +1,194 @@
+use super::schema::{Reaction, ReactionDef, ReactionInsertion, ReactionTable};
+use crate::note::schema::NoteDef;
+
+use sea_query::{
+    DeleteStatement, Expr, Func, OnConflict, PostgresQueryBuilder as QueryBuilder, Query,
+    SimpleExpr,
+};
+use sea_query_binder::SqlxBinder;
+use sqlx::{PgConnection as Connection, Result as SqlxResult};
+
+const REACTION_COLUMNS: [ReactionDef; 7] = [
+    ReactionDef::Id,
+    ReactionDef::UserId,
+    ReactionDef::ObjectUri,
+    ReactionDef::ActivityId,
+    ReactionDef::AuthorUri,
+    ReactionDef::AuthorInbox,
+    ReactionDef::CreatedAt,
+];
+
+/// Inserts a reaction. Returns false if the user has already reacted to the object.
+pub async fn insert_reaction(
+    conn: &mut Connection,
+    table: ReactionTable,
+    insertion: ReactionInsertion,
+) -> SqlxResult<bool> {
+    let (query, values) = Query::insert()
+        .into_table(table)
+        .columns([
+            ReactionDef::Id,
+            ReactionDef::UserId,
+            ReactionDef::ObjectUri,
+            ReactionDef::ActivityId,
+            ReactionDef::AuthorUri,
+            ReactionDef::AuthorInbox,
+        ])
+        .values([
+            insertion.id.into(),
+            insertion.user_id.into(),
+            insertion.object_uri.into(),
+            insertion.activity_id.into(),
+            insertion.author_uri.into(),
+            insertion.author_inbox.into(),
+        ])
+        .expect("failed to encode")
+        .on_conflict(
+            OnConflict::columns([ReactionDef::UserId, ReactionDef::ObjectUri])
+                .do_nothing()
+                .to_owned(),
+        )
+        .build_sqlx(QueryBuilder);
+
+    let result = sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub async fn find_reaction_by_pair(
+    conn: &mut Connection,
+    table: ReactionTable,
+    user_id: &str,
+    object_uri: &str,
+) -> SqlxResult<Option<Reaction>> {
+    find_reaction(
+        conn,
+        table,
+        Expr::col(ReactionDef::UserId)
+            .eq(user_id)
+            .and(Expr::col(ReactionDef::ObjectUri).eq(object_uri)),
+    )
+    .await
+}
+
+pub async fn find_reaction_by_activity_id(
+    conn: &mut Connection,
+    table: ReactionTable,
+    activity_id: &str,
+) -> SqlxResult<Option<Reaction>> {
+    find_reaction(
+        conn,
+        table,
+        Expr::col(ReactionDef::ActivityId).eq(activity_id),
+    )
+    .await
+}
+
+pub async fn delete_reaction(
+    conn: &mut Connection,
+    table: ReactionTable,
+    reaction_id: &str,
+) -> SqlxResult<()> {
+    let (query, values) = Query::delete()
+        .from_table(table)
+        .and_where(Expr::col(ReactionDef::Id).eq(reaction_id))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn delete_reactions_to(
+    conn: &mut Connection,
+    table: ReactionTable,
+    object_uri: &str,
+) -> SqlxResult<()> {
+    let (query, values) = Query::delete()
+        .from_table(table)
+        .and_where(Expr::col(ReactionDef::ObjectUri).eq(object_uri))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+/// Deletes reactions to notes of the user, whose URIs are `notes_url` followed by note IDs.
+/// Should be called before the notes are deleted.
+pub async fn delete_reactions_to_notes_of(
+    conn: &mut Connection,
+    table: ReactionTable,
+    user_id: &str,
+    notes_url: &str,
+) -> SqlxResult<()> {
+    let (query, values) = reactions_to_notes_of(table, user_id, notes_url).build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn count_reactions(
+    conn: &mut Connection,
+    table: ReactionTable,
+    object_uri: &str,
+) -> SqlxResult<usize> {
+    let (query, values) = Query::select()
+        .expr(Func::count(Expr::col(ReactionDef::Id)))
+        .from(table)
+        .and_where(Expr::col(ReactionDef::ObjectUri).eq(object_uri))
+        .build_sqlx(QueryBuilder);
+
+    let (count,): (i64,) = sqlx::query_as_with(&query, values)
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(count as usize)
+}
+
+async fn find_reaction(
+    conn: &mut Connection,
+    table: ReactionTable,
+    condition: SimpleExpr,
+) -> SqlxResult<Option<Reaction>> {
+    let (query, values) = Query::select()
+        .columns(REACTION_COLUMNS)
+        .from(table)
+        .and_where(condition)
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+fn reactions_to_notes_of(table: ReactionTable, user_id: &str, notes_url: &str) -> DeleteStatement {
+    Query::delete()
+        .from_table(table)
+        .and_where(
+            Expr::col(ReactionDef::ObjectUri).in_subquery(
+                Query::select()
+                    .expr(Expr::cust_with_values(r#"$1 || "id""#, [notes_url]))
+                    .from(NoteDef::Table)
+                    .and_where(Expr::col(NoteDef::UserId).eq(user_id))
+                    .to_owned(),
+            ),
+        )
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reactions_to_notes_of;
+    use crate::reaction::schema::ReactionTable;
+
+    use sea_query::PostgresQueryBuilder;
+
+    #[test]
+    fn reactions_to_notes_of_selects_note_uris() {
+        let query =
+            reactions_to_notes_of(ReactionTable::Likes, "alice", "https://example.com/notes/")
+                .to_string(PostgresQueryBuilder);
+        assert_eq!(
+            query,
+            r#"DELETE FROM "likes" WHERE "object_uri" IN (SELECT 'https://example.com/notes/' || "id" FROM "notes" WHERE "user_id" = 'alice')"#
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use sea_query::Iden;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Tables of reactions, which share the same columns.
+#[derive(Debug, Clone, Copy, Iden)]
+pub enum ReactionTable {
+    #[iden = "likes"]
+    Likes,
+    #[iden = "announces"]
+    Announces,
+}
+
+#[derive(Debug, Clone, Copy, Iden)]
+pub enum ReactionDef {
+    Id,
+    UserId,
+    ObjectUri,
+    ActivityId,
+    AuthorUri,
+    AuthorInbox,
+    CreatedAt,
+}
+
+#[derive(Debug)]
+pub struct ReactionInsertion {
+    pub id: String,
+    pub user_id: String,
+    pub object_uri: String,
+    pub activity_id: String,
+    pub author_uri: Option<String>,
+    pub author_inbox: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Reaction {
+    pub id: String,
+    pub user_id: String,
+    pub object_uri: String,
+    pub activity_id: String,
+    pub author_uri: Option<String>,
+    pub author_inbox: Option<String>,
+    pub created_at: OffsetDateTime,
+}
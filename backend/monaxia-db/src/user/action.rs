@@ -1,9 +1,51 @@
-use super::schema::{LocalUser, LocalUserDef, LocalUserInsertion, UserDef, UserInsertion};
+use super::schema::{
+    LocalUser, LocalUserDef, LocalUserInsertion, LocalUserProfileUpdate, RemoteUser, UserDef,
+    UserInsertion,
+};
+use crate::{
+    activity::schema::ActivityDef,
+    follow::schema::FollowDef,
+    reaction::schema::{ReactionDef, ReactionTable},
+};
 
-use sea_query::{Expr, Func, JoinType, PostgresQueryBuilder as QueryBuilder, Query};
+use sea_query::{
+    Expr, Func, JoinType, OnConflict, Order, PostgresQueryBuilder as QueryBuilder, Query,
+    SelectStatement, SimpleExpr, UpdateStatement,
+};
 use sea_query_binder::SqlxBinder;
 use sqlx::{PgConnection as Connection, Result as SqlxResult};
 
+const USER_INSERTION_COLUMNS: [UserDef; 12] = [
+    UserDef::Id,
+    UserDef::Username,
+    UserDef::Domain,
+    UserDef::PublicKey,
+    UserDef::Uri,
+    UserDef::KeyId,
+    UserDef::Inbox,
+    UserDef::SharedInbox,
+    UserDef::DisplayName,
+    UserDef::Description,
+    UserDef::AvatarUrl,
+    UserDef::FetchedAt,
+];
+
+const REMOTE_USER_COLUMNS: [UserDef; 13] = [
+    UserDef::Id,
+    UserDef::IdSeq,
+    UserDef::Username,
+    UserDef::Domain,
+    UserDef::PublicKey,
+    UserDef::Uri,
+    UserDef::KeyId,
+    UserDef::Inbox,
+    UserDef::SharedInbox,
+    UserDef::DisplayName,
+    UserDef::Description,
+    UserDef::AvatarUrl,
+    UserDef::FetchedAt,
+];
+
 pub async fn fetch_local_users_count(conn: &mut Connection) -> SqlxResult<usize> {
     let (query, _) = Query::select()
         .expr(Func::count(Expr::col(LocalUserDef::UserId)))
@@ -36,18 +78,8 @@ pub async fn local_user_occupied(conn: &mut Connection, username: &str) -> SqlxR
 pub async fn register_user(conn: &mut Connection, insertion: UserInsertion) -> SqlxResult<()> {
     let (query, values) = Query::insert()
         .into_table(UserDef::Table)
-        .columns([
-            UserDef::Id,
-            UserDef::Username,
-            UserDef::Domain,
-            UserDef::PublicKey,
-        ])
-        .values([
-            insertion.id.into(),
-            insertion.username.into(),
-            insertion.domain.into(),
-            insertion.public_key.into(),
-        ])
+        .columns(USER_INSERTION_COLUMNS)
+        .values(user_insertion_values(insertion))
         .expect("failed to encode")
         .build_sqlx(QueryBuilder);
 
@@ -55,6 +87,60 @@ pub async fn register_user(conn: &mut Connection, insertion: UserInsertion) -> S
     Ok(())
 }
 
+/// Inserts a remote user, or overwrites properties of the user with the same URI.
+/// Returns the ID of the user.
+pub async fn upsert_remote_user(
+    conn: &mut Connection,
+    insertion: UserInsertion,
+) -> SqlxResult<String> {
+    let uri = insertion.uri.as_deref().expect("remote user must have URI");
+    let (query, values) =
+        release_stale_acct(&insertion.username, &insertion.domain, uri).build_sqlx(QueryBuilder);
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+
+    let (query, values) = Query::insert()
+        .into_table(UserDef::Table)
+        .columns(USER_INSERTION_COLUMNS)
+        .values(user_insertion_values(insertion))
+        .expect("failed to encode")
+        .on_conflict(
+            OnConflict::column(UserDef::Uri)
+                .update_columns([
+                    UserDef::Username,
+                    UserDef::PublicKey,
+                    UserDef::KeyId,
+                    UserDef::Inbox,
+                    UserDef::SharedInbox,
+                    UserDef::DisplayName,
+                    UserDef::Description,
+                    UserDef::AvatarUrl,
+                    UserDef::FetchedAt,
+                ])
+                .to_owned(),
+        )
+        .returning_col(UserDef::Id)
+        .build_sqlx(QueryBuilder);
+
+    let (id,): (String,) = sqlx::query_as_with(&query, values)
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(id)
+}
+
+/// Releases the acct held by another remote user, which happens when the account
+/// has been renamed or the handle has been recycled.
+/// The stale username is replaced with its URI, which never matches as username,
+/// until the user is fetched again.
+fn release_stale_acct(username: &str, domain: &str, uri: &str) -> UpdateStatement {
+    Query::update()
+        .table(UserDef::Table)
+        .value(UserDef::Username, Expr::col(UserDef::Uri))
+        .cond_where(Expr::col(UserDef::Username).eq(username))
+        .and_where(Expr::col(UserDef::Domain).eq(domain))
+        .and_where(Expr::col(UserDef::Uri).ne(uri))
+        .to_owned()
+}
+
 pub async fn register_local_user<'a>(
     conn: &'a mut Connection,
     insertion: LocalUserInsertion<'a>,
@@ -74,24 +160,7 @@ pub async fn find_local_user_by_username(
     conn: &mut Connection,
     username: &str,
 ) -> SqlxResult<Option<LocalUser>> {
-    let (query, values) = Query::select()
-        .expr_as(Expr::col((UserDef::Table, UserDef::Id)), UserDef::Id)
-        .expr_as(Expr::col((UserDef::Table, UserDef::IdSeq)), UserDef::IdSeq)
-        .expr_as(
-            Expr::col((UserDef::Table, UserDef::Username)),
-            UserDef::Username,
-        )
-        .expr_as(
-            Expr::col((UserDef::Table, UserDef::PublicKey)),
-            UserDef::PublicKey,
-        )
-        .from(LocalUserDef::Table)
-        .join(
-            JoinType::InnerJoin,
-            UserDef::Table,
-            Expr::col((LocalUserDef::Table, LocalUserDef::UserId))
-                .equals((UserDef::Table, UserDef::Id)),
-        )
+    let (query, values) = select_local_user()
         .cond_where(Expr::col((UserDef::Table, UserDef::Username)).eq(username))
         .build_sqlx(QueryBuilder);
 
@@ -101,21 +170,214 @@ pub async fn find_local_user_by_username(
     Ok(row)
 }
 
+/// Fetches all local users in order of registration.
+pub async fn fetch_local_users(conn: &mut Connection) -> SqlxResult<Vec<LocalUser>> {
+    let (query, values) = select_local_user()
+        .order_by((UserDef::Table, UserDef::IdSeq), Order::Asc)
+        .build_sqlx(QueryBuilder);
+
+    let rows = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows)
+}
+
 pub async fn find_local_user_by_id(
     conn: &mut Connection,
     user_id: &str,
 ) -> SqlxResult<Option<LocalUser>> {
+    let (query, values) = select_local_user()
+        .cond_where(Expr::col((UserDef::Table, UserDef::Id)).eq(user_id))
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+pub async fn update_local_user_profile(
+    conn: &mut Connection,
+    user_id: &str,
+    update: LocalUserProfileUpdate,
+) -> SqlxResult<()> {
+    let (query, values) = Query::update()
+        .table(UserDef::Table)
+        .values([
+            (UserDef::DisplayName, update.display_name.into()),
+            (UserDef::Description, update.description.into()),
+            (UserDef::AvatarUrl, update.avatar_url.into()),
+            (UserDef::HeaderUrl, update.header_url.into()),
+            (
+                UserDef::ManuallyApprovesFollowers,
+                update.manually_approves_followers.into(),
+            ),
+            (UserDef::ProfileFields, update.profile_fields.into()),
+            (UserDef::AlsoKnownAs, update.also_known_as.into()),
+            (UserDef::MovedTo, update.moved_to.into()),
+        ])
+        .cond_where(Expr::col(UserDef::Id).eq(user_id))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn update_user_public_key(
+    conn: &mut Connection,
+    user_id: &str,
+    public_key: &str,
+) -> SqlxResult<()> {
+    let (query, values) = Query::update()
+        .table(UserDef::Table)
+        .values([(UserDef::PublicKey, public_key.into())])
+        .cond_where(Expr::col(UserDef::Id).eq(user_id))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn update_local_user_private_key(
+    conn: &mut Connection,
+    user_id: &str,
+    private_key: &str,
+) -> SqlxResult<()> {
+    let (query, values) = Query::update()
+        .table(LocalUserDef::Table)
+        .values([(LocalUserDef::PrivateKey, private_key.into())])
+        .cond_where(Expr::col(LocalUserDef::UserId).eq(user_id))
+        .build_sqlx(QueryBuilder);
+
+    sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    Ok(())
+}
+
+pub async fn fetch_local_user_private_key(
+    conn: &mut Connection,
+    user_id: &str,
+) -> SqlxResult<Option<String>> {
     let (query, values) = Query::select()
-        .expr_as(Expr::col((UserDef::Table, UserDef::Id)), UserDef::Id)
-        .expr_as(Expr::col((UserDef::Table, UserDef::IdSeq)), UserDef::IdSeq)
-        .expr_as(
-            Expr::col((UserDef::Table, UserDef::Username)),
-            UserDef::Username,
-        )
-        .expr_as(
-            Expr::col((UserDef::Table, UserDef::PublicKey)),
-            UserDef::PublicKey,
-        )
+        .column(LocalUserDef::PrivateKey)
+        .from(LocalUserDef::Table)
+        .cond_where(Expr::col(LocalUserDef::UserId).eq(user_id))
+        .build_sqlx(QueryBuilder);
+
+    let row: Option<(String,)> = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row.map(|(pk,)| pk))
+}
+
+pub async fn find_remote_user_by_uri(
+    conn: &mut Connection,
+    uri: &str,
+) -> SqlxResult<Option<RemoteUser>> {
+    let (query, values) = Query::select()
+        .columns(REMOTE_USER_COLUMNS)
+        .from(UserDef::Table)
+        .cond_where(Expr::col(UserDef::Uri).eq(uri))
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+pub async fn find_remote_user_by_key_id(
+    conn: &mut Connection,
+    key_id: &str,
+) -> SqlxResult<Option<RemoteUser>> {
+    let (query, values) = Query::select()
+        .columns(REMOTE_USER_COLUMNS)
+        .from(UserDef::Table)
+        .cond_where(Expr::col(UserDef::KeyId).eq(key_id))
+        .build_sqlx(QueryBuilder);
+
+    let row = sqlx::query_as_with(&query, values)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(row)
+}
+
+/// Fetches distinct inboxes of all known remote users.
+/// Shared inboxes are preferred if available.
+pub async fn fetch_remote_inboxes(conn: &mut Connection) -> SqlxResult<Vec<String>> {
+    let (query, values) = Query::select()
+        .distinct()
+        .expr(Func::coalesce([
+            Expr::col(UserDef::SharedInbox).into(),
+            Expr::col(UserDef::Inbox).into(),
+        ]))
+        .from(UserDef::Table)
+        .and_where(Expr::col(UserDef::Inbox).is_not_null())
+        .build_sqlx(QueryBuilder);
+
+    let rows: Vec<(String,)> = sqlx::query_as_with(&query, values)
+        .fetch_all(&mut *conn)
+        .await?;
+    Ok(rows.into_iter().map(|(inbox,)| inbox).collect())
+}
+
+/// Deletes the user and rows depending on it, except notes.
+/// Should be called in a transaction.
+pub async fn delete_user(conn: &mut Connection, user_id: &str) -> SqlxResult<()> {
+    let statements = [
+        Query::delete()
+            .from_table(FollowDef::Table)
+            .cond_where(
+                Expr::col(FollowDef::FollowerId)
+                    .eq(user_id)
+                    .or(Expr::col(FollowDef::FolloweeId).eq(user_id)),
+            )
+            .to_owned(),
+        Query::delete()
+            .from_table(ReactionTable::Likes)
+            .and_where(Expr::col(ReactionDef::UserId).eq(user_id))
+            .to_owned(),
+        Query::delete()
+            .from_table(ReactionTable::Announces)
+            .and_where(Expr::col(ReactionDef::UserId).eq(user_id))
+            .to_owned(),
+        Query::delete()
+            .from_table(ActivityDef::Table)
+            .and_where(Expr::col(ActivityDef::UserId).eq(user_id))
+            .to_owned(),
+        Query::delete()
+            .from_table(LocalUserDef::Table)
+            .and_where(Expr::col(LocalUserDef::UserId).eq(user_id))
+            .to_owned(),
+        Query::delete()
+            .from_table(UserDef::Table)
+            .and_where(Expr::col(UserDef::Id).eq(user_id))
+            .to_owned(),
+    ];
+    for statement in statements {
+        let (query, values) = statement.build_sqlx(QueryBuilder);
+        sqlx::query_with(&query, values).execute(&mut *conn).await?;
+    }
+    Ok(())
+}
+
+fn select_local_user() -> SelectStatement {
+    let columns = [
+        UserDef::Id,
+        UserDef::IdSeq,
+        UserDef::CreatedAt,
+        UserDef::Username,
+        UserDef::PublicKey,
+        UserDef::DisplayName,
+        UserDef::Description,
+        UserDef::AvatarUrl,
+        UserDef::HeaderUrl,
+        UserDef::ManuallyApprovesFollowers,
+        UserDef::ProfileFields,
+        UserDef::AlsoKnownAs,
+        UserDef::MovedTo,
+    ];
+    Query::select()
+        .columns(columns.map(|c| (UserDef::Table, c)))
         .from(LocalUserDef::Table)
         .join(
             JoinType::InnerJoin,
@@ -123,11 +385,39 @@ pub async fn find_local_user_by_id(
             Expr::col((LocalUserDef::Table, LocalUserDef::UserId))
                 .equals((UserDef::Table, UserDef::Id)),
         )
-        .cond_where(Expr::col((UserDef::Table, UserDef::Id)).eq(user_id))
-        .build_sqlx(QueryBuilder);
+        .to_owned()
+}
 
-    let row = sqlx::query_as_with(&query, values)
-        .fetch_optional(&mut *conn)
-        .await?;
-    Ok(row)
+fn user_insertion_values(insertion: UserInsertion) -> [SimpleExpr; 12] {
+    [
+        insertion.id.into(),
+        insertion.username.into(),
+        insertion.domain.into(),
+        insertion.public_key.into(),
+        insertion.uri.into(),
+        insertion.key_id.into(),
+        insertion.inbox.into(),
+        insertion.shared_inbox.into(),
+        insertion.display_name.into(),
+        insertion.description.into(),
+        insertion.avatar_url.into(),
+        insertion.fetched_at.into(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::release_stale_acct;
+
+    use sea_query::PostgresQueryBuilder;
+
+    #[test]
+    fn releases_acct_of_other_uri_only() {
+        let query = release_stale_acct("alice", "example.com", "https://example.com/users/2")
+            .to_string(PostgresQueryBuilder);
+        assert_eq!(
+            query,
+            r#"UPDATE "users" SET "username" = "uri" WHERE "username" = 'alice' AND "domain" = 'example.com' AND "uri" <> 'https://example.com/users/2'"#
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use sea_query::Iden;
+use serde_json::Value as JsonValue;
 use sqlx::FromRow;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone, Copy, Iden)]
 pub enum UserDef {
@@ -7,11 +9,23 @@ pub enum UserDef {
     Table,
     Id,
     IdSeq,
+    CreatedAt,
     Username,
     Domain,
     PublicKey,
     DisplayName,
     Description,
+    Uri,
+    KeyId,
+    Inbox,
+    SharedInbox,
+    AvatarUrl,
+    FetchedAt,
+    HeaderUrl,
+    ManuallyApprovesFollowers,
+    ProfileFields,
+    AlsoKnownAs,
+    MovedTo,
 }
 
 #[derive(Debug, Clone, Copy, Iden)]
@@ -28,6 +42,14 @@ pub struct UserInsertion {
     pub username: String,
     pub domain: String,
     pub public_key: String,
+    pub uri: Option<String>,
+    pub key_id: Option<String>,
+    pub inbox: Option<String>,
+    pub shared_inbox: Option<String>,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub avatar_url: Option<String>,
+    pub fetched_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug)]
@@ -36,10 +58,48 @@ pub struct LocalUserInsertion<'a> {
     pub private_key: &'a str, // this struct does not clear
 }
 
+#[derive(Debug)]
+pub struct LocalUserProfileUpdate {
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub avatar_url: Option<String>,
+    pub header_url: Option<String>,
+    pub manually_approves_followers: bool,
+    pub profile_fields: JsonValue,
+    pub also_known_as: JsonValue,
+    pub moved_to: Option<String>,
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct LocalUser {
     pub id: String,
     pub id_seq: i64,
+    pub created_at: OffsetDateTime,
     pub username: String,
     pub public_key: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub avatar_url: Option<String>,
+    pub header_url: Option<String>,
+    pub manually_approves_followers: bool,
+    pub profile_fields: JsonValue,
+    pub also_known_as: JsonValue,
+    pub moved_to: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RemoteUser {
+    pub id: String,
+    pub id_seq: i64,
+    pub username: String,
+    pub domain: String,
+    pub public_key: String,
+    pub uri: String,
+    pub key_id: String,
+    pub inbox: Option<String>,
+    pub shared_inbox: Option<String>,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub avatar_url: Option<String>,
+    pub fetched_at: Option<OffsetDateTime>,
 }
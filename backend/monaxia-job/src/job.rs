@@ -19,6 +19,14 @@ impl MxJob {
         }
     }
 
+    pub fn new_retrying(payload: Job, max_retry: usize, backoff: Backoff) -> MxJob {
+        MxJob {
+            payload,
+            tag: Default::default(),
+            retry: Retry::new(max_retry, backoff),
+        }
+    }
+
     pub fn job(&self) -> &Job {
         &self.payload
     }
@@ -28,9 +36,7 @@ impl MxJob {
     }
 
     pub fn next(self) -> Option<(MxJob, Duration)> {
-        let Some((delay, retry)) = self.retry.retry() else {
-            return None;
-        };
+        let (delay, retry) = self.retry.retry()?;
 
         let next_job = MxJob {
             payload: self.payload,
@@ -45,4 +51,28 @@ impl MxJob {
 pub enum Job {
     /// Server has started.
     Hello,
+
+    /// An activity has been posted to inbox.
+    ProcessInbox {
+        /// Local user ID of the inbox owner. `None` if posted to shared inbox.
+        recipient: Option<String>,
+
+        /// Actor URI of the verified signer.
+        signer: String,
+
+        /// Serialized activity JSON in canonical form.
+        activity: String,
+    },
+
+    /// An activity should be delivered to remote inbox.
+    Deliver {
+        /// Local user ID of the actor, whose key signs the request.
+        actor_id: String,
+
+        /// Inbox URL.
+        inbox: String,
+
+        /// Serialized activity JSON.
+        activity: String,
+    },
 }
@@ -74,7 +74,7 @@ where
     Ok(sender)
 }
 
-async fn create_amqp_receiver_queue<T: Debug>(
+async fn create_amqp_receiver_queue<T>(
     conn: &Connection,
     worker_suffix: &str,
 ) -> Result<ReceiverQueue<T>>
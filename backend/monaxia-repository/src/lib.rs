@@ -10,4 +10,9 @@ pub struct Container {
     pub migration: Arc<dyn repo::migration::MigrationRepository>,
     pub user: Arc<dyn repo::user::UserRepository>,
     pub domain: Arc<dyn repo::domain::DomainRepository>,
+    pub activity: Arc<dyn repo::activity::ActivityRepository>,
+    pub follow: Arc<dyn repo::follow::FollowRepository>,
+    pub note: Arc<dyn repo::note::NoteRepository>,
+    pub instance: Arc<dyn repo::instance::InstanceRepository>,
+    pub reaction: Arc<dyn repo::reaction::ReactionRepository>,
 }
@@ -1,5 +1,10 @@
+pub mod activity;
 pub mod domain;
+pub mod follow;
+pub mod instance;
 pub mod migration;
+pub mod note;
+pub mod reaction;
 pub mod user;
 
 pub trait Repository: Send + Sync + 'static {}
@@ -0,0 +1,27 @@
+use super::Repository;
+use crate::RepoResult;
+
+use async_trait::async_trait;
+use monaxia_data::{
+    activity::{Activity, ActivityRegistration},
+    pagination::Pagination,
+};
+
+#[async_trait]
+pub trait ActivityRepository: Repository {
+    /// Records an activity performed by a local user.
+    async fn register_activity(&self, registration: ActivityRegistration) -> RepoResult<()>;
+
+    /// Finds an activity by ID.
+    async fn find_activity(&self, activity_id: &str) -> RepoResult<Option<Activity>>;
+
+    /// Counts public activities of the user.
+    async fn count_outbox(&self, user_id: &str) -> RepoResult<usize>;
+
+    /// Fetches public activities of the user.
+    async fn fetch_outbox(
+        &self,
+        user_id: &str,
+        pagination: &Pagination,
+    ) -> RepoResult<Vec<Activity>>;
+}
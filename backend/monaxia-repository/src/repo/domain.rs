@@ -2,9 +2,25 @@ use super::Repository;
 use crate::RepoResult;
 
 use async_trait::async_trait;
+use monaxia_data::domain::{DomainPolicy, ModeratedDomain};
 
 #[async_trait]
 pub trait DomainRepository: Repository {
     /// Records the domain as acknowledged. Returns true if it was first acknowledgement.
     async fn acknowledge(&self, domain: &str) -> RepoResult<bool>;
+
+    /// Finds the policy applied to the domain. Policies of parent domains are
+    /// also applied, and the most specific one wins.
+    async fn find_policy(&self, domain: &str) -> RepoResult<DomainPolicy>;
+
+    /// Sets the policy of the domain. `DomainPolicy::None` lifts the restriction.
+    async fn set_policy(
+        &self,
+        domain: &str,
+        policy: DomainPolicy,
+        reason: Option<&str>,
+    ) -> RepoResult<()>;
+
+    /// Fetches all domains with policy other than none.
+    async fn fetch_moderated(&self) -> RepoResult<Vec<ModeratedDomain>>;
 }
@@ -0,0 +1,63 @@
+use super::Repository;
+use crate::RepoResult;
+
+use async_trait::async_trait;
+use monaxia_data::{
+    follow::{Follow, FollowRegistration, FollowRelated},
+    pagination::Pagination,
+};
+
+#[async_trait]
+pub trait FollowRepository: Repository {
+    /// Registers follow relationship and returns the ID of it.
+    /// If the relationship already exists, its state and activity ID are overwritten.
+    async fn register_follow(&self, registration: FollowRegistration) -> RepoResult<String>;
+
+    /// Finds a follow relationship.
+    async fn find_follow(&self, follow_find: FollowFind<'_>) -> RepoResult<Option<Follow>>;
+
+    /// Marks the follow relationship as accepted.
+    async fn accept_follow(&self, follow_id: &str) -> RepoResult<()>;
+
+    /// Removes the follow relationship.
+    async fn remove_follow(&self, follow_id: &str) -> RepoResult<()>;
+
+    /// Fetches distinct inboxes of accepted remote followers.
+    /// Shared inboxes are preferred if available.
+    async fn fetch_follower_inboxes(&self, user_id: &str) -> RepoResult<Vec<String>>;
+
+    /// Fetches IDs of local users who follow the user, including pending requests.
+    async fn fetch_local_follower_ids(&self, user_id: &str) -> RepoResult<Vec<String>>;
+
+    /// Fetches followers whose follow requests to the user are pending.
+    async fn fetch_follow_requests(&self, user_id: &str) -> RepoResult<Vec<FollowRelated>>;
+
+    /// Counts accepted followers or followees of the user.
+    async fn count_related(&self, user_id: &str, direction: FollowDirection) -> RepoResult<usize>;
+
+    /// Fetches accepted followers or followees of the user.
+    async fn fetch_related(
+        &self,
+        user_id: &str,
+        direction: FollowDirection,
+        pagination: &Pagination,
+    ) -> RepoResult<Vec<FollowRelated>>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum FollowFind<'a> {
+    Pair {
+        follower_id: &'a str,
+        followee_id: &'a str,
+    },
+    ActivityId(&'a str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowDirection {
+    /// Users who follow the user.
+    Followers,
+
+    /// Users whom the user follows.
+    Following,
+}
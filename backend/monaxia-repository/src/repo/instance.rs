@@ -0,0 +1,20 @@
+use super::Repository;
+use crate::RepoResult;
+
+use async_trait::async_trait;
+use monaxia_data::instance::{InstanceActor, InstanceActorRegistration};
+
+#[async_trait]
+pub trait InstanceRepository: Repository {
+    /// Finds the instance actor. Returns `None` if its key pair is not generated yet.
+    async fn find_instance_actor(&self) -> RepoResult<Option<InstanceActor>>;
+
+    /// Registers the instance actor. Returns false if it already exists.
+    async fn register_instance_actor(
+        &self,
+        registration: InstanceActorRegistration,
+    ) -> RepoResult<bool>;
+
+    /// Fetches PEM-encoded private key of the instance actor.
+    async fn fetch_private_key(&self) -> RepoResult<Option<String>>;
+}
@@ -0,0 +1,22 @@
+use super::Repository;
+use crate::RepoResult;
+
+use async_trait::async_trait;
+use monaxia_data::note::{Note, NoteRegistration, NoteTombstone};
+
+#[async_trait]
+pub trait NoteRepository: Repository {
+    /// Registers a note.
+    async fn register_note(&self, registration: NoteRegistration) -> RepoResult<()>;
+
+    /// Finds a note by ID.
+    async fn find_note(&self, note_id: &str) -> RepoResult<Option<Note>>;
+
+    /// Replaces the note with a tombstone, and removes its Create activities and
+    /// reactions to it at once. `note_uri` is the URI of the note.
+    /// Returns false if the note does not exist.
+    async fn delete_note(&self, note_id: &str, note_uri: &str) -> RepoResult<bool>;
+
+    /// Finds a tombstone of the deleted note by ID.
+    async fn find_tombstone(&self, note_id: &str) -> RepoResult<Option<NoteTombstone>>;
+}
@@ -0,0 +1,36 @@
+use super::Repository;
+use crate::RepoResult;
+
+use async_trait::async_trait;
+use monaxia_data::reaction::{Reaction, ReactionKind, ReactionRegistration};
+
+#[async_trait]
+pub trait ReactionRepository: Repository {
+    /// Registers a reaction. Returns false if the user has already reacted to the object.
+    async fn register_reaction(&self, registration: ReactionRegistration) -> RepoResult<bool>;
+
+    /// Finds a reaction.
+    async fn find_reaction(
+        &self,
+        kind: ReactionKind,
+        reaction_find: ReactionFind<'_>,
+    ) -> RepoResult<Option<Reaction>>;
+
+    /// Removes the reaction.
+    async fn remove_reaction(&self, kind: ReactionKind, reaction_id: &str) -> RepoResult<()>;
+
+    /// Removes all reactions to the object.
+    async fn remove_reactions_to(&self, object_uri: &str) -> RepoResult<()>;
+
+    /// Counts reactions to the object.
+    async fn count_reactions(&self, kind: ReactionKind, object_uri: &str) -> RepoResult<usize>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ReactionFind<'a> {
+    Pair {
+        user_id: &'a str,
+        object_uri: &'a str,
+    },
+    ActivityId(&'a str),
+}
@@ -2,7 +2,10 @@ use super::Repository;
 use crate::RepoResult;
 
 use async_trait::async_trait;
-use monaxia_data::user::{LocalUser, LocalUserRegistration, RemoteUserRegistration};
+use monaxia_data::user::{
+    LocalUser, LocalUserKeyRotation, LocalUserProfile, LocalUserRegistration, RemoteUser,
+    RemoteUserRegistration,
+};
 
 #[async_trait]
 pub trait UserRepository: Repository {
@@ -20,8 +23,8 @@ pub trait UserRepository: Repository {
         domain: &str,
     ) -> RepoResult<String>;
 
-    /// Registers new remote user and returns the ID of the user.
-    /// Domain must be registered before this.
+    /// Registers new remote user or updates the one with the same URI,
+    /// and returns the ID of the user. Domain must be registered before this.
     async fn register_remote_user(
         &self,
         registration: RemoteUserRegistration,
@@ -30,6 +33,37 @@ pub trait UserRepository: Repository {
 
     /// Finds a local user by username.
     async fn find_local_user(&self, user_find: UserFind<'_>) -> RepoResult<Option<LocalUser>>;
+
+    /// Fetches all local users in order of registration.
+    async fn fetch_local_users(&self) -> RepoResult<Vec<LocalUser>>;
+
+    /// Overwrites the profile of a local user.
+    async fn update_local_profile(
+        &self,
+        user_id: &str,
+        profile: LocalUserProfile,
+    ) -> RepoResult<()>;
+
+    /// Replaces the key pair of a local user.
+    async fn rotate_key(&self, user_id: &str, rotation: LocalUserKeyRotation) -> RepoResult<()>;
+
+    /// Fetches PEM-encoded private key of a local user.
+    async fn fetch_private_key(&self, user_id: &str) -> RepoResult<Option<String>>;
+
+    /// Removes the user with their relationships, reactions and activities.
+    /// Notes of the user are replaced with tombstones, and reactions to them are removed.
+    /// `notes_url` is the prefix of local note URIs followed by note IDs.
+    async fn remove_user(&self, user_id: &str, notes_url: &str) -> RepoResult<()>;
+
+    /// Fetches distinct inboxes of all known remote users.
+    /// Shared inboxes are preferred if available.
+    async fn fetch_remote_inboxes(&self) -> RepoResult<Vec<String>>;
+
+    /// Finds a remote user by actor URI or key ID.
+    async fn find_remote_user(
+        &self,
+        remote_user_find: RemoteUserFind<'_>,
+    ) -> RepoResult<Option<RemoteUser>>;
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -37,3 +71,9 @@ pub enum UserFind<'a> {
     Username(&'a str),
     UserId(&'a str),
 }
+
+#[derive(Debug, Clone, Copy)]
+pub enum RemoteUserFind<'a> {
+    Uri(&'a str),
+    KeyId(&'a str),
+}
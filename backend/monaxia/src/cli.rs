@@ -1,8 +1,12 @@
+mod domain;
 mod migrate;
+mod note;
 mod user;
 
 use self::{
+    domain::{execute_domain_subcommand, DomainSubcommand},
     migrate::{execute_migrate_subcommand, MigrateSubcommand},
+    note::{execute_note_subcommand, NoteSubcommand},
     user::{execute_user_subcommand, UserSubcommand},
 };
 use crate::web::run_server;
@@ -43,6 +47,14 @@ pub enum Subcommand {
     #[clap(subcommand)]
     User(UserSubcommand),
 
+    /// Note manipulation.
+    #[clap(subcommand)]
+    Note(NoteSubcommand),
+
+    /// Domain moderation.
+    #[clap(subcommand)]
+    Domain(DomainSubcommand),
+
     /// Database migration.
     Migrate(MigrateSubcommand),
 }
@@ -53,6 +65,8 @@ pub async fn execute_cli(args: Arguments) -> Result<()> {
     match args.subcommand {
         Subcommand::Serve => run_server(config).await?,
         Subcommand::User(s) => execute_user_subcommand(config, s).await?,
+        Subcommand::Note(s) => execute_note_subcommand(config, s).await?,
+        Subcommand::Domain(s) => execute_domain_subcommand(config, s).await?,
         Subcommand::Migrate(s) => execute_migrate_subcommand(config, s).await?,
     }
     Ok(())
@@ -0,0 +1,103 @@
+use crate::repository_impl::construct_container_db;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use monaxia_data::{config::Config, domain::DomainPolicy};
+use monaxia_repository::Container;
+
+#[derive(Debug, Clone, Parser)]
+pub enum DomainSubcommand {
+    /// Apply moderation policy to a remote domain and its subdomains.
+    Block {
+        /// Domain (like `example.com`), with port part if needed.
+        domain: String,
+
+        /// Policy to apply (silence, suspend or reject-media).
+        #[clap(short, long, default_value = "suspend")]
+        policy: DomainPolicy,
+
+        /// Reason shown in the list.
+        #[clap(long)]
+        reason: Option<String>,
+    },
+
+    /// Lift moderation policy of a remote domain.
+    Unblock {
+        /// Domain (like `example.com`), with port part if needed.
+        domain: String,
+    },
+
+    /// List domains with moderation policy.
+    List,
+}
+
+pub async fn execute_domain_subcommand(config: Config, subcommand: DomainSubcommand) -> Result<()> {
+    let container = construct_container_db(&config).await?;
+    match subcommand {
+        DomainSubcommand::Block {
+            domain,
+            policy,
+            reason,
+        } => block_domain(config, container, &domain, policy, reason.as_deref()).await?,
+        DomainSubcommand::Unblock { domain } => unblock_domain(config, container, &domain).await?,
+        DomainSubcommand::List => list_domains(container).await?,
+    }
+
+    Ok(())
+}
+
+async fn block_domain(
+    config: Config,
+    container: Container,
+    domain: &str,
+    policy: DomainPolicy,
+    reason: Option<&str>,
+) -> Result<()> {
+    let domain = normalize_domain(&config, domain)?;
+    if policy == DomainPolicy::None {
+        bail!("Use unblock subcommand to lift the policy");
+    }
+
+    container.domain.set_policy(&domain, policy, reason).await?;
+    println!("Applied {} to {domain}", policy.as_str());
+    Ok(())
+}
+
+async fn unblock_domain(config: Config, container: Container, domain: &str) -> Result<()> {
+    let domain = normalize_domain(&config, domain)?;
+    container
+        .domain
+        .set_policy(&domain, DomainPolicy::None, None)
+        .await?;
+    println!("Lifted policy of {domain}");
+    Ok(())
+}
+
+async fn list_domains(container: Container) -> Result<()> {
+    let domains = container.domain.fetch_moderated().await?;
+    if domains.is_empty() {
+        println!("No domain is moderated");
+        return Ok(());
+    }
+
+    for domain in domains {
+        println!(
+            "{}\t{}\t{}",
+            domain.domain,
+            domain.policy.as_str(),
+            domain.reason.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+fn normalize_domain(config: &Config, domain: &str) -> Result<String> {
+    let domain = domain.trim().to_ascii_lowercase();
+    if domain.is_empty() || domain.contains(['/', '@', ' ']) {
+        bail!("Invalid domain {domain}");
+    }
+    if domain == config.cached.acct_origin() {
+        bail!("Local domain cannot be moderated");
+    }
+    Ok(domain)
+}
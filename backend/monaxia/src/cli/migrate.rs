@@ -1,4 +1,4 @@
-use crate::repository_impl::construct_container_db;
+use crate::{federation::instance::ensure_instance_actor, repository_impl::construct_container_db};
 
 use std::{
     env::{current_dir, var as env_var, VarError},
@@ -97,6 +97,8 @@ async fn execute_migration(container: Container) -> Result<()> {
             .await?;
     }
 
+    ensure_instance_actor(&container).await?;
+
     Ok(())
 }
 
@@ -0,0 +1,190 @@
+use crate::{
+    federation::{
+        instance::instance_client,
+        local::{local_note_id_of, local_note_url},
+        note::{create_note, delete_note, text_to_html},
+        reaction,
+    },
+    repository_impl::construct_container_db,
+    worker::create_producer,
+};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use inquire::Text;
+use monaxia_data::{config::Config, note::NoteVisibility, reaction::ReactionKind};
+use monaxia_repository::{repo::user::UserFind, Container};
+use url::Url;
+
+#[derive(Debug, Clone, Parser)]
+pub enum NoteSubcommand {
+    /// Post new note.
+    Create {
+        /// Username of the author.
+        username: String,
+
+        /// Plain text content. Prompted if omitted.
+        content: Option<String>,
+
+        /// Visibility of the note (public, unlisted or followers).
+        #[clap(short, long, default_value = "public")]
+        visibility: NoteVisibility,
+
+        /// URI of the object replying to.
+        #[clap(long)]
+        in_reply_to: Option<Url>,
+    },
+
+    /// Delete a local note and send Delete to followers.
+    Delete {
+        /// ID or URL of the note.
+        note: String,
+    },
+
+    /// Like a note.
+    Like(ReactionOptions),
+
+    /// Undo Like of a note.
+    Unlike(ReactionOptions),
+
+    /// Announce (boost) a note.
+    Announce(ReactionOptions),
+
+    /// Undo Announce of a note.
+    Unannounce(ReactionOptions),
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct ReactionOptions {
+    /// Username of the local user.
+    username: String,
+
+    /// URI of the note, either local or remote.
+    object: Url,
+}
+
+pub async fn execute_note_subcommand(config: Config, subcommand: NoteSubcommand) -> Result<()> {
+    let container = construct_container_db(&config).await?;
+    match subcommand {
+        NoteSubcommand::Create {
+            username,
+            content,
+            visibility,
+            in_reply_to,
+        } => {
+            post_note(
+                config,
+                container,
+                &username,
+                content,
+                visibility,
+                in_reply_to,
+            )
+            .await?
+        }
+        NoteSubcommand::Delete { note } => remove_note(config, container, &note).await?,
+        NoteSubcommand::Like(options) => {
+            react_note(config, container, options, ReactionKind::Like, true).await?
+        }
+        NoteSubcommand::Unlike(options) => {
+            react_note(config, container, options, ReactionKind::Like, false).await?
+        }
+        NoteSubcommand::Announce(options) => {
+            react_note(config, container, options, ReactionKind::Announce, true).await?
+        }
+        NoteSubcommand::Unannounce(options) => {
+            react_note(config, container, options, ReactionKind::Announce, false).await?
+        }
+    }
+
+    Ok(())
+}
+
+async fn post_note(
+    config: Config,
+    container: Container,
+    username: &str,
+    content: Option<String>,
+    visibility: NoteVisibility,
+    in_reply_to: Option<Url>,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let content = match content {
+        Some(c) => c,
+        None => Text::new("Content:").prompt()?,
+    };
+    if content.trim().is_empty() {
+        bail!("Content is empty");
+    }
+
+    let producer = create_producer(&config).await?;
+    let note = create_note(
+        &config,
+        &container,
+        &producer,
+        &local_user.id,
+        text_to_html(&content),
+        in_reply_to.map(|u| u.to_string()),
+        visibility,
+    )
+    .await?;
+
+    println!("Posted {}", local_note_url(&config, &note.id));
+    Ok(())
+}
+
+async fn remove_note(config: Config, container: Container, note: &str) -> Result<()> {
+    let note_id = local_note_id_of(&config, note).unwrap_or(note);
+    let Some(note) = container.note.find_note(note_id).await? else {
+        bail!("Note {note_id} not found");
+    };
+
+    let producer = create_producer(&config).await?;
+    delete_note(&config, &container, &producer, &note).await?;
+
+    println!("Deleted {}", local_note_url(&config, &note.id));
+    Ok(())
+}
+
+/// Sends Like or Announce, or Undo of it if `react` is false.
+async fn react_note(
+    config: Config,
+    container: Container,
+    options: ReactionOptions,
+    kind: ReactionKind,
+    react: bool,
+) -> Result<()> {
+    let username = &options.username;
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let client = instance_client(&config, &container).await?;
+    let producer = create_producer(&config).await?;
+    let (config, container) = (&config, &container);
+    let (user, object) = (&local_user, &options.object);
+    let kind_name = kind.as_str();
+    if react {
+        if !reaction::react(config, container, &client, &producer, user, kind, object).await? {
+            bail!("{username} has already sent {kind_name} to {object}");
+        }
+        println!("Sent {kind_name} to {object}");
+    } else {
+        if !reaction::unreact(config, container, &client, &producer, user, kind, object).await? {
+            bail!("{username} has not sent {kind_name} to {object}");
+        }
+        println!("Undid {kind_name} of {object}");
+    }
+    Ok(())
+}
@@ -1,46 +1,372 @@
-use crate::repository_impl::construct_container_db;
+use crate::{
+    constant::KEY_LENGTH,
+    federation::{
+        actor::{delete_account, rotate_key, update_profile},
+        client::ApClient,
+        follow::{approve_follow, follow_remote_user, reject_follow, unfollow_remote_user},
+        instance::instance_client,
+        local::local_user_url,
+        moving::move_account,
+        note::{escape_html, text_to_html},
+        resolver::{resolve_acct, resolve_actor},
+    },
+    repository_impl::construct_container_db,
+    worker::create_producer,
+};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use clap::Parser;
 use inquire::{validator::Validation, Confirm, Text};
 use monaxia_data::{
+    ap::Acct,
     config::Config,
-    user::{validate_username_format, LocalUserRegistration},
+    follow::FollowState,
+    user::{validate_username_format, LocalUser, LocalUserRegistration, ProfileField, RemoteUser},
+};
+use monaxia_repository::{
+    repo::{follow::FollowFind, user::UserFind},
+    Container,
 };
-use monaxia_repository::Container;
 use rand::prelude::*;
 use rsa::{
-    pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding},
+    pkcs1::DecodeRsaPrivateKey,
+    pkcs8::{DecodePrivateKey, EncodePrivateKey, EncodePublicKey, LineEnding},
+    traits::PublicKeyParts,
     RsaPrivateKey,
 };
-
-pub const KEY_LENGTH: usize = 2048;
+use serde::Serialize;
+use serde_json::json;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use url::Url;
 
 #[derive(Debug, Clone, Parser)]
 pub enum UserSubcommand {
     /// Create new user.
-    Create,
+    Create(CreateOptions),
+
+    /// List local users.
+    List {
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Show a local user.
+    Show {
+        /// Username or ID of the local user.
+        user: String,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Edit profile of a local user and send Update to followers.
+    Edit(ProfileOptions),
+
+    /// Replace the key pair of a local user and send Update to followers.
+    RotateKey {
+        /// Username of the local user.
+        username: String,
+
+        /// Proceeds without confirmation.
+        #[clap(short, long)]
+        yes: bool,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Delete a local user after sending Delete to all known servers.
+    Delete {
+        /// Username of the local user.
+        username: String,
+
+        /// Proceeds without confirmation.
+        #[clap(short, long)]
+        yes: bool,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Add or remove an alias (`alsoKnownAs`) of a local user, which allows
+    /// moving from the aliased account.
+    Alias {
+        /// Username of the local user.
+        username: String,
+
+        /// Actor URI or handle (like `@user@example.com`) of the other account.
+        actor: String,
+
+        /// Removes the alias instead of adding.
+        #[clap(long)]
+        remove: bool,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Move a local user to another account and notify followers.
+    /// The target must list the local user as its alias beforehand.
+    Move {
+        /// Username of the local user.
+        username: String,
+
+        /// Actor URI or handle (like `@user@example.com`) of the target account.
+        target: String,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Follow a remote user.
+    Follow {
+        /// Username of the local user.
+        username: String,
+
+        /// Actor URI or handle (like `@user@example.com`) of the remote user.
+        actor: String,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Unfollow a remote user.
+    Unfollow {
+        /// Username of the local user.
+        username: String,
+
+        /// Actor URI or handle (like `@user@example.com`) of the remote user.
+        actor: String,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// List, accept or reject pending follow requests to a local user.
+    FollowRequests {
+        /// Username of the local user.
+        username: String,
+
+        #[clap(subcommand)]
+        action: FollowRequestAction,
+    },
+}
+
+#[derive(Debug, Clone, Parser)]
+pub enum FollowRequestAction {
+    /// List pending follow requests.
+    List {
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Accept a follow request and send Accept.
+    Accept {
+        /// Actor URI or handle (like `@user@example.com`) of the requester.
+        actor: String,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Reject a follow request and send Reject.
+    Reject {
+        /// Actor URI or handle (like `@user@example.com`) of the requester.
+        actor: String,
+
+        /// Prints the result as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct CreateOptions {
+    /// Username of the new user. Prompted if omitted.
+    #[clap(long)]
+    username: Option<String>,
+
+    /// PEM file of an existing RSA private key (PKCS#8 or PKCS#1) to use
+    /// instead of generating new one, for salvaging a user.
+    #[clap(long, value_name = "FILE")]
+    private_key_file: Option<PathBuf>,
+
+    /// Proceeds without confirmation.
+    #[clap(short, long)]
+    yes: bool,
+
+    /// Prints the created user as JSON, with the private key if generated.
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Parser)]
+pub struct ProfileOptions {
+    /// Username of the local user.
+    username: String,
+
+    /// Display name. Empty string removes it.
+    #[clap(long)]
+    display_name: Option<String>,
+
+    /// Plain text biography. Empty string removes it.
+    #[clap(long)]
+    summary: Option<String>,
+
+    /// Avatar image URL. Empty string removes it.
+    #[clap(long)]
+    avatar_url: Option<String>,
+
+    /// Header image URL. Empty string removes it.
+    #[clap(long)]
+    header_url: Option<String>,
+
+    /// Holds follow requests until accepted or rejected by `follow-requests`.
+    #[clap(long)]
+    manually_approves_followers: Option<bool>,
+
+    /// Profile field as `name=value`. Replaces all existing fields.
+    #[clap(long = "field", value_name = "NAME=VALUE")]
+    fields: Vec<String>,
+
+    /// Removes all profile fields.
+    #[clap(long, conflicts_with = "fields")]
+    clear_fields: bool,
+
+    /// Prints the updated user as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+/// Local user in JSON output.
+#[derive(Debug, Serialize)]
+struct UserView {
+    id: String,
+    username: String,
+    acct: String,
+    uri: Url,
+    display_name: Option<String>,
+    also_known_as: Vec<String>,
+    moved_to: Option<String>,
+    public_key: String,
+
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+impl UserView {
+    fn new(config: &Config, local_user: &LocalUser) -> UserView {
+        let profile = &local_user.profile;
+        UserView {
+            id: local_user.id.clone(),
+            username: local_user.username.clone(),
+            acct: format!("{}@{}", local_user.username, config.cached.acct_origin()),
+            uri: local_user_url(config, &local_user.id),
+            display_name: profile.display_name.clone(),
+            also_known_as: profile.also_known_as.clone(),
+            moved_to: profile.moved_to.clone(),
+            public_key: local_user.public_key.clone(),
+            created_at: local_user.created_at,
+        }
+    }
+}
+
+/// Created user in JSON output.
+#[derive(Debug, Serialize)]
+struct CreatedUserView {
+    #[serde(flatten)]
+    user: UserView,
+
+    /// PEM-encoded private key, only if generated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    private_key: Option<String>,
 }
 
 pub async fn execute_user_subcommand(config: Config, subcommand: UserSubcommand) -> Result<()> {
     let container = construct_container_db(&config).await?;
     match subcommand {
-        UserSubcommand::Create => create_user(config, container).await?,
+        UserSubcommand::Create(options) => create_user(config, container, options).await?,
+        UserSubcommand::List { json } => list_users(config, container, json).await?,
+        UserSubcommand::Show { user, json } => show_user(config, container, &user, json).await?,
+        UserSubcommand::Edit(options) => edit_profile(config, container, options).await?,
+        UserSubcommand::RotateKey {
+            username,
+            yes,
+            json,
+        } => rotate_user_key(config, container, &username, yes, json).await?,
+        UserSubcommand::Delete {
+            username,
+            yes,
+            json,
+        } => delete_user(config, container, &username, yes, json).await?,
+        UserSubcommand::Alias {
+            username,
+            actor,
+            remove,
+            json,
+        } => set_alias(config, container, &username, &actor, remove, json).await?,
+        UserSubcommand::Move {
+            username,
+            target,
+            json,
+        } => move_user(config, container, &username, &target, json).await?,
+        UserSubcommand::Follow {
+            username,
+            actor,
+            json,
+        } => follow_user(config, container, &username, &actor, json).await?,
+        UserSubcommand::Unfollow {
+            username,
+            actor,
+            json,
+        } => unfollow_user(config, container, &username, &actor, json).await?,
+        UserSubcommand::FollowRequests { username, action } => match action {
+            FollowRequestAction::List { json } => {
+                list_follow_requests(container, &username, json).await?
+            }
+            FollowRequestAction::Accept { actor, json } => {
+                respond_follow_request(config, container, &username, &actor, true, json).await?
+            }
+            FollowRequestAction::Reject { actor, json } => {
+                respond_follow_request(config, container, &username, &actor, false, json).await?
+            }
+        },
     }
 
     Ok(())
 }
 
-async fn create_user(config: Config, container: Container) -> Result<()> {
+async fn create_user(config: Config, container: Container, options: CreateOptions) -> Result<()> {
     let username_range = 1..=(config.user.username_max_length);
-    let username = Text::new("Username:")
-        .with_validator(move |n: &str| {
-            Ok(validate_username_format(n, username_range.clone())
-                .map_or_else(|e| Validation::Invalid(e.into()), |_| Validation::Valid))
-        })
-        .prompt()?;
+    let username = match options.username {
+        Some(username) => {
+            validate_username_format(&username, username_range)?;
+            username
+        }
+        None => Text::new("Username:")
+            .with_validator(move |n: &str| {
+                Ok(validate_username_format(n, username_range.clone())
+                    .map_or_else(|e| Validation::Invalid(e.into()), |_| Validation::Valid))
+            })
+            .prompt()?,
+    };
 
-    println!("Checking whether the username is available...");
+    eprintln!("Checking whether the username is available...");
     let banned_usernames = config.user.banned_usernames.clone();
     if banned_usernames.contains(&username) {
         bail!("Username {username} is banned by setting");
@@ -50,25 +376,40 @@ async fn create_user(config: Config, container: Container) -> Result<()> {
         bail!("Username {username} is already taken");
     }
 
-    println!("Generating new keypair...");
-    let mut rng = thread_rng();
-    let private_key = RsaPrivateKey::new(&mut rng, KEY_LENGTH)?;
-    let public_key = private_key.to_public_key();
-    let private_pkcs8_pem = private_key.to_pkcs8_pem(LineEnding::LF)?;
-    let public_pkcs8_pem = public_key.to_public_key_pem(LineEnding::LF)?;
+    let private_key = match &options.private_key_file {
+        Some(path) => {
+            eprintln!("Importing keypair from {}...", path.display());
+            read_private_key(path)?
+        }
+        None => {
+            eprintln!("Generating new keypair...");
+            let mut rng = thread_rng();
+            RsaPrivateKey::new(&mut rng, KEY_LENGTH)?
+        }
+    };
+    let generated_private_key = match options.private_key_file {
+        Some(_) => None,
+        None => Some(private_key.to_pkcs8_pem(LineEnding::LF)?),
+    };
+    if let (Some(private_pkcs8_pem), false) = (&generated_private_key, options.json) {
+        let public_key = private_key.to_public_key();
+        let public_pkcs8_pem = public_key.to_public_key_pem(LineEnding::LF)?;
 
-    println!("Generated keypair is below:");
-    println!("Private Key ~~~~~~~~~~~~~~~~~~~~~~~~~~~");
-    println!();
-    println!("{}", private_pkcs8_pem.as_str());
-    println!("Public Key ~~~~~~~~~~~~~~~~~~~~~~~~~~~~");
-    println!();
-    println!("{public_pkcs8_pem}");
-    println!("~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~");
+        println!("Generated keypair is below:");
+        println!("Private Key ~~~~~~~~~~~~~~~~~~~~~~~~~~~");
+        println!();
+        println!("{}", private_pkcs8_pem.as_str());
+        println!("Public Key ~~~~~~~~~~~~~~~~~~~~~~~~~~~~");
+        println!();
+        println!("{public_pkcs8_pem}");
+        println!("~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~");
+    }
 
-    match Confirm::new("You may save keypairs in case of salvaging user data. Proceed?").prompt() {
-        Ok(true) => (),
-        _ => bail!("User creation aborted"),
+    if !confirm(
+        "You may save keypairs in case of salvaging user data. Proceed?",
+        options.yes,
+    ) {
+        bail!("User creation aborted");
     }
 
     let local_origin = config.cached.acct_origin();
@@ -84,7 +425,491 @@ async fn create_user(config: Config, container: Container) -> Result<()> {
         )
         .await?;
 
-    println!("Registered successfully!");
-    println!("User ID is {user_id}");
+    if options.json {
+        let local_user = find_user(&container, &user_id).await?;
+        print_json(&CreatedUserView {
+            user: UserView::new(&config, &local_user),
+            private_key: generated_private_key.map(|pem| pem.to_string()),
+        })?;
+    } else {
+        println!("Registered successfully!");
+        println!("User ID is {user_id}");
+    }
+    Ok(())
+}
+
+async fn list_users(config: Config, container: Container, json: bool) -> Result<()> {
+    let local_users = container.user.fetch_local_users().await?;
+    if json {
+        let views: Vec<_> = local_users
+            .iter()
+            .map(|u| UserView::new(&config, u))
+            .collect();
+        return print_json(&views);
+    }
+
+    for local_user in local_users {
+        let display_name = local_user.profile.display_name.as_deref().unwrap_or("");
+        println!("{}\t{}\t{display_name}", local_user.id, local_user.username);
+    }
+    Ok(())
+}
+
+async fn show_user(config: Config, container: Container, user: &str, json: bool) -> Result<()> {
+    let local_user = find_user(&container, user).await?;
+    let view = UserView::new(&config, &local_user);
+    if json {
+        return print_json(&view);
+    }
+
+    println!("ID:           {}", view.id);
+    println!("Username:     {}", view.username);
+    println!("Acct:         {}", view.acct);
+    println!("URI:          {}", view.uri);
+    if let Some(display_name) = &view.display_name {
+        println!("Display name: {display_name}");
+    }
+    for alias in &view.also_known_as {
+        println!("Alias:        {alias}");
+    }
+    if let Some(moved_to) = &view.moved_to {
+        println!("Moved to:     {moved_to}");
+    }
+    println!("Created at:   {}", view.created_at.format(&Rfc3339)?);
+    println!("Public key:");
+    println!("{}", view.public_key);
+    Ok(())
+}
+
+async fn edit_profile(config: Config, container: Container, options: ProfileOptions) -> Result<()> {
+    let username = &options.username;
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let mut profile = local_user.profile.clone();
+    if let Some(display_name) = options.display_name {
+        profile.display_name = non_empty(display_name);
+    }
+    if let Some(summary) = options.summary {
+        profile.summary = non_empty(summary).map(|s| text_to_html(&s));
+    }
+    if let Some(avatar_url) = options.avatar_url {
+        profile.avatar_url = non_empty(avatar_url).map(validate_url).transpose()?;
+    }
+    if let Some(header_url) = options.header_url {
+        profile.header_url = non_empty(header_url).map(validate_url).transpose()?;
+    }
+    if let Some(manually_approves_followers) = options.manually_approves_followers {
+        profile.manually_approves_followers = manually_approves_followers;
+    }
+    if options.clear_fields {
+        profile.fields.clear();
+    } else if !options.fields.is_empty() {
+        profile.fields = options
+            .fields
+            .iter()
+            .map(|field| {
+                let Some((name, value)) = field.split_once('=') else {
+                    bail!("Field {field} must be formatted as NAME=VALUE");
+                };
+                Ok(ProfileField {
+                    name: name.trim().to_string(),
+                    value: escape_html(value.trim()),
+                })
+            })
+            .collect::<Result<_>>()?;
+    }
+
+    let producer = create_producer(&config).await?;
+    let local_user = update_profile(&config, &container, &producer, &local_user, profile).await?;
+
+    if options.json {
+        return print_json(&UserView::new(&config, &local_user));
+    }
+    println!("Updated profile of {username}");
+    Ok(())
+}
+
+async fn rotate_user_key(
+    config: Config,
+    container: Container,
+    username: &str,
+    yes: bool,
+    json: bool,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let prompt = format!(
+        "Current key of {username} will be discarded and signatures by it will be rejected. Proceed?"
+    );
+    if !confirm(&prompt, yes) {
+        bail!("Key rotation aborted");
+    }
+
+    eprintln!("Generating new keypair...");
+    let mut rng = thread_rng();
+    let private_key = RsaPrivateKey::new(&mut rng, KEY_LENGTH)?;
+    let producer = create_producer(&config).await?;
+    let local_user = rotate_key(&config, &container, &producer, &local_user, private_key).await?;
+
+    if json {
+        return print_json(&UserView::new(&config, &local_user));
+    }
+    println!("New public key of {username} is below:");
+    println!("{}", local_user.public_key);
+    Ok(())
+}
+
+async fn delete_user(
+    config: Config,
+    container: Container,
+    username: &str,
+    yes: bool,
+    json: bool,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let prompt = format!("User {username} and all of their data will be deleted. Proceed?");
+    if !confirm(&prompt, yes) {
+        bail!("User deletion aborted");
+    }
+
+    eprintln!("Sending Delete to known servers...");
+    let client = instance_client(&config, &container).await?;
+    let (delivered, total) = delete_account(&config, &container, &client, &local_user).await?;
+
+    if json {
+        return print_json(&json!({
+            "id": local_user.id,
+            "username": local_user.username,
+            "delivered": delivered,
+            "inboxes": total,
+        }));
+    }
+    println!("Delivered to {delivered} of {total} inboxes");
+    println!("Deleted user {username}");
+    Ok(())
+}
+
+async fn set_alias(
+    config: Config,
+    container: Container,
+    username: &str,
+    actor: &str,
+    remove: bool,
+    json: bool,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let client = instance_client(&config, &container).await?;
+    let alias = resolve_remote_user(&container, &client, actor).await?;
+    let mut profile = local_user.profile.clone();
+    let aliased = profile.also_known_as.contains(&alias.uri);
+    match (remove, aliased) {
+        (false, false) => profile.also_known_as.push(alias.uri.clone()),
+        (true, true) => profile.also_known_as.retain(|a| a != &alias.uri),
+        (false, true) => bail!("{} is already an alias of {username}", alias.uri),
+        (true, false) => bail!("{} is not an alias of {username}", alias.uri),
+    }
+
+    let producer = create_producer(&config).await?;
+    let local_user = update_profile(&config, &container, &producer, &local_user, profile).await?;
+
+    if json {
+        return print_json(&UserView::new(&config, &local_user));
+    }
+    if remove {
+        println!("Removed alias {} from {username}", alias.uri);
+    } else {
+        println!("Added alias {} to {username}", alias.uri);
+    }
+    Ok(())
+}
+
+async fn move_user(
+    config: Config,
+    container: Container,
+    username: &str,
+    target: &str,
+    json: bool,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let client = instance_client(&config, &container).await?;
+    let producer = create_producer(&config).await?;
+    let target = resolve_remote_user(&container, &client, target).await?;
+    let target_uri = Url::parse(&target.uri)?;
+    let local_user = move_account(
+        &config,
+        &container,
+        &client,
+        &producer,
+        &local_user,
+        &target_uri,
+    )
+    .await?;
+
+    if json {
+        return print_json(&UserView::new(&config, &local_user));
+    }
+    println!(
+        "Moved {} to {}",
+        local_user_url(&config, &local_user.id),
+        target.uri
+    );
+    Ok(())
+}
+
+async fn follow_user(
+    config: Config,
+    container: Container,
+    username: &str,
+    actor: &str,
+    json: bool,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let client = instance_client(&config, &container).await?;
+    let producer = create_producer(&config).await?;
+    let remote_user = resolve_remote_user(&container, &client, actor).await?;
+    follow_remote_user(&config, &container, &producer, &local_user, &remote_user).await?;
+
+    if json {
+        return print_json(&json!({
+            "username": local_user.username,
+            "actor": remote_user.uri,
+            "state": FollowState::Pending.as_str(),
+        }));
+    }
+    println!("Sent follow request to {}", remote_user.uri);
+    Ok(())
+}
+
+async fn unfollow_user(
+    config: Config,
+    container: Container,
+    username: &str,
+    actor: &str,
+    json: bool,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let client = instance_client(&config, &container).await?;
+    let producer = create_producer(&config).await?;
+    let remote_user = resolve_remote_user(&container, &client, actor).await?;
+    if !unfollow_remote_user(&config, &container, &producer, &local_user, &remote_user).await? {
+        bail!("{username} does not follow {}", remote_user.uri);
+    }
+
+    if json {
+        return print_json(&json!({
+            "username": local_user.username,
+            "actor": remote_user.uri,
+        }));
+    }
+    println!("Unfollowed {}", remote_user.uri);
+    Ok(())
+}
+
+async fn list_follow_requests(container: Container, username: &str, json: bool) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let requests = container
+        .follow
+        .fetch_follow_requests(&local_user.id)
+        .await?;
+    if json {
+        let views: Vec<_> = requests
+            .iter()
+            .map(|r| json!({ "user_id": r.user_id, "uri": r.uri }))
+            .collect();
+        return print_json(&views);
+    }
+
+    for request in requests {
+        println!("{}", request.uri.as_deref().unwrap_or(&request.user_id));
+    }
+    Ok(())
+}
+
+async fn respond_follow_request(
+    config: Config,
+    container: Container,
+    username: &str,
+    actor: &str,
+    accept: bool,
+    json: bool,
+) -> Result<()> {
+    let Some(local_user) = container
+        .user
+        .find_local_user(UserFind::Username(username))
+        .await?
+    else {
+        bail!("User {username} not found");
+    };
+
+    let client = instance_client(&config, &container).await?;
+    let remote_user = resolve_remote_user(&container, &client, actor).await?;
+    let follow = container
+        .follow
+        .find_follow(FollowFind::Pair {
+            follower_id: &remote_user.id,
+            followee_id: &local_user.id,
+        })
+        .await?
+        .filter(|f| f.state == FollowState::Pending);
+    let Some(follow) = follow else {
+        bail!(
+            "{} has no pending follow request to {username}",
+            remote_user.uri
+        );
+    };
+
+    let producer = create_producer(&config).await?;
+    if accept {
+        approve_follow(
+            &config,
+            &container,
+            &producer,
+            &local_user,
+            &remote_user,
+            &follow,
+        )
+        .await?;
+    } else {
+        reject_follow(
+            &config,
+            &container,
+            &producer,
+            &local_user,
+            &remote_user,
+            &follow,
+        )
+        .await?;
+    }
+
+    if json {
+        return print_json(&json!({
+            "username": local_user.username,
+            "actor": remote_user.uri,
+            "accepted": accept,
+        }));
+    }
+    let response = if accept { "Accepted" } else { "Rejected" };
+    println!("{response} follow request from {}", remote_user.uri);
     Ok(())
 }
+
+/// Finds a local user by username, or by ID if no user has the username.
+async fn find_user(container: &Container, user: &str) -> Result<LocalUser> {
+    let local_user = match container
+        .user
+        .find_local_user(UserFind::Username(user))
+        .await?
+    {
+        Some(local_user) => Some(local_user),
+        None => {
+            container
+                .user
+                .find_local_user(UserFind::UserId(user))
+                .await?
+        }
+    };
+    local_user.ok_or_else(|| anyhow!("User {user} not found"))
+}
+
+/// Reads RSA private key from PEM file, either PKCS#8 or PKCS#1 encoded.
+/// Keys shorter than `KEY_LENGTH` bits are rejected.
+fn read_private_key(path: &Path) -> Result<RsaPrivateKey> {
+    let pem = fs::read_to_string(path)?;
+    let private_key = RsaPrivateKey::from_pkcs8_pem(&pem)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(&pem))
+        .map_err(|_| anyhow!("{} is not a valid RSA private key", path.display()))?;
+    let bits = private_key.n().bits();
+    if bits < KEY_LENGTH {
+        bail!("RSA private key must be at least {KEY_LENGTH} bits, but it has {bits} bits");
+    }
+    Ok(private_key)
+}
+
+/// Asks confirmation unless `--yes` is specified.
+fn confirm(prompt: &str, yes: bool) -> bool {
+    yes || Confirm::new(prompt)
+        .with_default(false)
+        .prompt()
+        .unwrap_or(false)
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn validate_url(url: String) -> Result<String> {
+    Url::parse(&url)?;
+    Ok(url)
+}
+
+/// Resolves a remote user from actor URI or handle.
+async fn resolve_remote_user(
+    container: &Container,
+    client: &ApClient,
+    actor: &str,
+) -> Result<RemoteUser> {
+    let remote_user = match Url::parse(actor) {
+        Ok(actor_uri) => resolve_actor(container, client, &actor_uri).await?,
+        Err(_) => resolve_acct(container, client, &Acct::parse(actor)?).await?,
+    };
+    Ok(remote_user)
+}
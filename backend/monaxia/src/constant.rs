@@ -2,7 +2,20 @@ pub const SOFTWARE_NAME: &str = "monaxia";
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 // pub const VERSION_TAG: &str = concat!(env!("CARGO_PKG_VERSION"), "-", env!("GIT_COMMIT_HASH"));
 
+/// Bit length of RSA keys generated for actors.
+pub const KEY_LENGTH: usize = 2048;
+
 pub mod mime {
     pub const APPLICATION_ACTIVITY_JSON: &str = "application/activity+json";
     pub const APPLICATION_LD_JSON: &str = "application/ld+json";
+    pub const APPLICATION_LD_JSON_ACTIVITYSTREAMS: &str =
+        r#"application/ld+json; profile="https://www.w3.org/ns/activitystreams""#;
+    pub const APPLICATION_JRD_JSON: &str = "application/jrd+json";
+    pub const APPLICATION_XRD_XML: &str = "application/xrd+xml";
+}
+
+pub mod ap {
+    pub const ACTIVITYSTREAMS_CONTEXT: &str = "https://www.w3.org/ns/activitystreams";
+    pub const SECURITY_CONTEXT: &str = "https://w3id.org/security/v1";
+    pub const PUBLIC_COLLECTION: &str = "https://www.w3.org/ns/activitystreams#Public";
 }
@@ -0,0 +1,17 @@
+pub mod actor;
+pub mod client;
+pub mod delivery;
+pub mod error;
+pub mod follow;
+pub mod instance;
+pub mod json;
+pub mod local;
+pub mod moderation;
+pub mod moving;
+pub mod note;
+pub mod reaction;
+pub mod resolver;
+pub mod signature;
+pub mod webfinger;
+
+pub use self::error::{Error as FederationError, Result as FederationResult};
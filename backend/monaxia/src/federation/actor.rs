@@ -0,0 +1,345 @@
+use super::{
+    client::{ApClient, ApSigner},
+    delivery::enqueue_deliveries,
+    local::{local_activity_url, local_note_url, local_profile_url, local_user_url},
+    moderation::ensure_not_suspended,
+    FederationError, FederationResult,
+};
+use crate::constant::ap::{ACTIVITYSTREAMS_CONTEXT, PUBLIC_COLLECTION, SECURITY_CONTEXT};
+
+use futures::{future, stream, StreamExt};
+use monaxia_data::{
+    activity::ActivityRegistration,
+    ap::vocabulary::{
+        Actor, ActorEndpoints, ActorKind, AnyObject, IriOrObject, Object, ObjectKind, OneOrMany,
+        PropertyValue, PropertyValueKind, PublicKey,
+    },
+    config::Config,
+    id::now_order58,
+    user::{LocalUser, LocalUserKeyRotation, LocalUserProfile},
+};
+use monaxia_job::job::MxJob;
+use monaxia_queue::job::Producer;
+use monaxia_repository::{repo::user::UserFind, Container};
+use rsa::RsaPrivateKey;
+use serde_json::{json, Value as JsonValue};
+use tracing::warn;
+use url::Url;
+
+/// Number of concurrent deliveries of Delete(Person).
+const ACCOUNT_DELETION_CONCURRENCY: usize = 8;
+
+/// Constructs Person object of the local user without `@context`.
+pub fn local_actor(config: &Config, local_user: &LocalUser) -> Actor {
+    let user_url = local_user_url(config, &local_user.id);
+    let collection_url = |name: &str| Url::parse(&format!("{user_url}/{name}")).expect("URL error");
+    let pubkey_id = {
+        let mut url = user_url.clone();
+        url.set_fragment(Some("main-key"));
+        url
+    };
+    let profile = &local_user.profile;
+    let fields: Vec<_> = profile
+        .fields
+        .iter()
+        .map(|f| {
+            AnyObject::PropertyValue(PropertyValue {
+                kind: PropertyValueKind::PropertyValue,
+                name: f.name.clone(),
+                value: f.value.clone(),
+            })
+        })
+        .collect();
+
+    let mut actor = Actor::new(ActorKind::Person, user_url.clone(), collection_url("inbox"));
+    actor.preferred_username = Some(local_user.username.clone());
+    actor.name = profile.display_name.clone();
+    actor.summary = profile.summary.clone();
+    actor.url = Some(IriOrObject::Iri(local_profile_url(config, &local_user.username)).into());
+    actor.icon = profile.avatar_url.as_deref().and_then(image_object);
+    actor.image = profile.header_url.as_deref().and_then(image_object);
+    actor.outbox = Some(collection_url("outbox"));
+    actor.followers = Some(collection_url("followers"));
+    actor.following = Some(collection_url("following"));
+    actor.endpoints = Some(ActorEndpoints {
+        shared_inbox: Some(
+            config
+                .cached
+                .server_base_url()
+                .join("/inbox")
+                .expect("URL error"),
+        ),
+    });
+    actor.public_key = Some(PublicKey {
+        id: pubkey_id.to_string(),
+        owner: user_url,
+        public_key_pem: local_user.public_key.clone(),
+    });
+    actor.manually_approves_followers = Some(profile.manually_approves_followers);
+    actor.discoverable = Some(true);
+    actor.published = Some(local_user.created_at);
+    actor.attachment = (!fields.is_empty()).then_some(fields.into());
+    let aliases: Vec<_> = profile
+        .also_known_as
+        .iter()
+        .filter_map(|a| Url::parse(a).ok())
+        .collect();
+    actor.also_known_as = (!aliases.is_empty()).then_some(aliases.into());
+    actor.moved_to = profile.moved_to.as_deref().and_then(|m| Url::parse(m).ok());
+    actor
+}
+
+/// Constructs Person object of the local user which only exposes the public key,
+/// for unsigned fetches under authorized fetch mode.
+pub fn local_actor_key_only(config: &Config, local_user: &LocalUser) -> Actor {
+    let actor = local_actor(config, local_user);
+    let mut key_only = Actor::new(actor.kind, actor.id, actor.inbox);
+    key_only.preferred_username = actor.preferred_username;
+    key_only.endpoints = actor.endpoints;
+    key_only.public_key = actor.public_key;
+    key_only
+}
+
+/// `@context` of documents which contain actor objects.
+pub fn actor_context() -> JsonValue {
+    json!([
+        ACTIVITYSTREAMS_CONTEXT,
+        SECURITY_CONTEXT,
+        {
+            "toot": "http://joinmastodon.org/ns#",
+            "schema": "http://schema.org#",
+            "discoverable": "toot:discoverable",
+            "manuallyApprovesFollowers": "as:manuallyApprovesFollowers",
+            "alsoKnownAs": { "@id": "as:alsoKnownAs", "@type": "@id" },
+            "movedTo": { "@id": "as:movedTo", "@type": "@id" },
+            "PropertyValue": "schema:PropertyValue",
+            "value": "schema:value",
+        },
+    ])
+}
+
+/// Saves the profile of the local user and sends Update(Person) to followers.
+pub async fn update_profile(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    local_user: &LocalUser,
+    profile: LocalUserProfile,
+) -> FederationResult<LocalUser> {
+    container
+        .user
+        .update_local_profile(&local_user.id, profile.clone())
+        .await?;
+    let local_user = LocalUser {
+        profile,
+        ..local_user.clone()
+    };
+
+    send_actor_update(config, container, producer, &local_user).await?;
+    Ok(local_user)
+}
+
+/// Replaces the key pair of the local user and sends Update(Person) to followers,
+/// so that remote servers refresh the cached public key.
+pub async fn rotate_key(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    local_user: &LocalUser,
+    private_key: RsaPrivateKey,
+) -> FederationResult<LocalUser> {
+    container
+        .user
+        .rotate_key(&local_user.id, LocalUserKeyRotation { private_key })
+        .await?;
+    let local_user = container
+        .user
+        .find_local_user(UserFind::UserId(&local_user.id))
+        .await?
+        .ok_or_else(|| {
+            FederationError::InvalidObject(format!("local user {} vanished", local_user.id))
+        })?;
+
+    send_actor_update(config, container, producer, &local_user).await?;
+    Ok(local_user)
+}
+
+/// Records Update(Person) of the local user and delivers it to followers.
+async fn send_actor_update(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    local_user: &LocalUser,
+) -> FederationResult<()> {
+    let actor_url = local_user_url(config, &local_user.id);
+    let activity_id = now_order58();
+    let update = json!({
+        "@context": actor_context(),
+        "id": local_activity_url(config, &activity_id),
+        "type": "Update",
+        "actor": actor_url,
+        "to": [PUBLIC_COLLECTION],
+        "cc": [format!("{actor_url}/followers")],
+        "object": local_actor(config, local_user),
+    });
+    container
+        .activity
+        .register_activity(ActivityRegistration {
+            id: activity_id,
+            user_id: local_user.id.clone(),
+            activity_type: "Update".to_string(),
+            body: update.clone(),
+            public: true,
+        })
+        .await?;
+
+    let inboxes = container
+        .follow
+        .fetch_follower_inboxes(&local_user.id)
+        .await?;
+    enqueue_deliveries(producer, &local_user.id, inboxes, &update).await
+}
+
+/// Delivers Delete(Person) to all known inboxes and removes the local user.
+/// Deliveries are performed immediately since the private key is removed together,
+/// and failed ones are not retried.
+/// Returns the numbers of succeeded deliveries and known inboxes.
+pub async fn delete_account(
+    config: &Config,
+    container: &Container,
+    client: &ApClient,
+    local_user: &LocalUser,
+) -> FederationResult<(usize, usize)> {
+    let actor_url = local_user_url(config, &local_user.id);
+    let delete = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &now_order58()),
+        "type": "Delete",
+        "actor": actor_url,
+        "to": [PUBLIC_COLLECTION],
+        "object": actor_url,
+    });
+
+    let signer = ApSigner::load_local_user(config, container, &local_user.id).await?;
+    let inboxes = container.user.fetch_remote_inboxes().await?;
+    let total = inboxes.len();
+    let (signer, delete) = (&signer, &delete);
+    let delivered = stream::iter(inboxes)
+        .map(|inbox| async move {
+            let result = deliver_delete(container, client, signer, &inbox, delete).await;
+            if let Err(e) = &result {
+                warn!("failed to deliver Delete to {inbox}: {e}");
+            }
+            result.is_ok()
+        })
+        .buffer_unordered(ACCOUNT_DELETION_CONCURRENCY)
+        .filter(|delivered| future::ready(*delivered))
+        .count()
+        .await;
+
+    container
+        .user
+        .remove_user(&local_user.id, local_note_url(config, "").as_str())
+        .await?;
+    Ok((delivered, total))
+}
+
+async fn deliver_delete(
+    container: &Container,
+    client: &ApClient,
+    signer: &ApSigner,
+    inbox: &str,
+    delete: &JsonValue,
+) -> FederationResult<()> {
+    let inbox = Url::parse(inbox).map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+    ensure_not_suspended(container, &inbox).await?;
+    client.deliver(&inbox, delete, signer).await
+}
+
+fn image_object(url: &str) -> Option<OneOrMany<IriOrObject<AnyObject>>> {
+    let url = Url::parse(url).ok()?;
+    let mut image = Object::new(ObjectKind::Image);
+    image.url = Some(IriOrObject::Iri(url).into());
+    Some(IriOrObject::Object(Box::new(AnyObject::Object(Box::new(image)))).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{actor_context, local_actor};
+
+    use monaxia_data::{
+        config::Config,
+        user::{LocalUser, LocalUserProfile, ProfileField},
+    };
+    use serde_json::json;
+    use time::OffsetDateTime;
+
+    #[test]
+    fn local_actor_serializes() {
+        let mut config = Config::default();
+        config.warmup();
+        let local_user = LocalUser {
+            id: "alice-id".into(),
+            id_seq: "1".into(),
+            username: "alice".into(),
+            public_key: "PUBLIC KEY".into(),
+            profile: LocalUserProfile {
+                display_name: Some("Alice".into()),
+                manually_approves_followers: true,
+                fields: vec![ProfileField {
+                    name: "Site".into(),
+                    value: "example".into(),
+                }],
+                also_known_as: vec!["https://example.com/users/alice".into()],
+                moved_to: Some("https://example.com/users/alice".into()),
+                ..Default::default()
+            },
+            created_at: OffsetDateTime::UNIX_EPOCH,
+        };
+
+        let actor = serde_json::to_value(local_actor(&config, &local_user)).expect("JSON error");
+        let base = config
+            .cached
+            .server_base_url()
+            .as_str()
+            .trim_end_matches('/');
+        let user_url = format!("{base}/users/alice-id");
+        assert_eq!(actor["type"], "Person");
+        assert_eq!(actor["id"], json!(user_url));
+        assert_eq!(actor["inbox"], json!(format!("{user_url}/inbox")));
+        assert_eq!(actor["preferredUsername"], "alice");
+        assert_eq!(actor["name"], "Alice");
+        assert_eq!(actor["manuallyApprovesFollowers"], true);
+        assert_eq!(
+            actor["endpoints"]["sharedInbox"],
+            json!(format!("{base}/inbox"))
+        );
+        assert_eq!(
+            actor["publicKey"]["id"],
+            json!(format!("{user_url}#main-key"))
+        );
+        assert_eq!(actor["publicKey"]["owner"], json!(user_url));
+        assert_eq!(actor["attachment"][0]["type"], "PropertyValue");
+        assert_eq!(
+            actor["alsoKnownAs"],
+            json!(["https://example.com/users/alice"])
+        );
+        assert_eq!(actor["movedTo"], "https://example.com/users/alice");
+
+        let context = actor_context();
+        let terms = &context[2];
+        for term in [
+            "manuallyApprovesFollowers",
+            "discoverable",
+            "alsoKnownAs",
+            "movedTo",
+            "PropertyValue",
+            "value",
+        ] {
+            assert!(
+                terms.get(term).is_some(),
+                "{term} is not defined in context"
+            );
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use super::{
+    local::{local_instance_actor_url, local_user_url},
+    signature::{digest_header, sign, signing_string, SignatureHeader, REQUEST_TARGET},
+    FederationError, FederationResult,
+};
+use crate::constant::{
+    mime::{APPLICATION_ACTIVITY_JSON, APPLICATION_JRD_JSON, APPLICATION_LD_JSON_ACTIVITYSTREAMS},
+    SOFTWARE_NAME, VERSION,
+};
+
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use http::{
+    header::{ACCEPT, CONTENT_TYPE, DATE, HOST},
+    HeaderMap, HeaderValue, Method,
+};
+use httpdate::fmt_http_date;
+use monaxia_data::{
+    ap::{acct_origin_of, context::canonicalize},
+    config::Config,
+};
+use monaxia_repository::Container;
+use reqwest::{Client, StatusCode};
+use rsa::{pkcs8::DecodePrivateKey, RsaPrivateKey};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value as JsonValue;
+use url::Url;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const DIGEST: &str = "digest";
+const SIGNATURE: &str = "signature";
+
+/// HTTP client for communicating with remote servers.
+#[derive(Debug, Clone)]
+pub struct ApClient {
+    client: Client,
+
+    /// Identity used for fetches without any local user context.
+    signer: Option<ApSigner>,
+}
+
+impl ApClient {
+    pub fn new(config: &Config) -> Result<ApClient> {
+        let user_agent = format!(
+            "{SOFTWARE_NAME}/{VERSION} (+{})",
+            config.cached.server_base_url()
+        );
+        let client = Client::builder()
+            .user_agent(user_agent)
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(ApClient {
+            client,
+            signer: None,
+        })
+    }
+
+    /// Signs fetches made by `fetch` with the signer, typically the instance actor.
+    pub fn with_signer(self, signer: ApSigner) -> ApClient {
+        ApClient {
+            signer: Some(signer),
+            ..self
+        }
+    }
+
+    /// Fetches an ActivityPub object, signed by the signer of this client if present.
+    pub async fn fetch<T: DeserializeOwned>(&self, url: &Url) -> FederationResult<T> {
+        if let Some(signer) = &self.signer {
+            return self.fetch_signed(url, signer).await;
+        }
+
+        let response = self
+            .client
+            .get(url.clone())
+            .header(ACCEPT, fetch_accept())
+            .send()
+            .await?
+            .error_for_status()?;
+        parse_document(response.json().await?)
+    }
+
+    /// Fetches a JSON resource descriptor (WebFinger response).
+    /// Returns `None` if the endpoint is missing, that is, it responds 404 without JSON.
+    pub async fn fetch_jrd<T: DeserializeOwned>(&self, url: &Url) -> FederationResult<Option<T>> {
+        let response = self
+            .client
+            .get(url.clone())
+            .header(ACCEPT, APPLICATION_JRD_JSON)
+            .send()
+            .await?;
+        let json_response = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("json"));
+        if response.status() == StatusCode::NOT_FOUND && !json_response {
+            return Ok(None);
+        }
+
+        let object = response.error_for_status()?.json().await?;
+        Ok(Some(object))
+    }
+
+    /// Fetches a document as text.
+    pub async fn fetch_text(&self, url: &Url, accept: &str) -> FederationResult<String> {
+        let response = self
+            .client
+            .get(url.clone())
+            .header(ACCEPT, accept)
+            .send()
+            .await?
+            .error_for_status()?;
+        let text = response.text().await?;
+        Ok(text)
+    }
+
+    /// Fetches an ActivityPub object with HTTP Signature.
+    pub async fn fetch_signed<T: DeserializeOwned>(
+        &self,
+        url: &Url,
+        signer: &ApSigner,
+    ) -> FederationResult<T> {
+        let headers = signer.signed_headers(&Method::GET, url, None)?;
+        let response = self
+            .client
+            .get(url.clone())
+            .headers(headers)
+            .header(ACCEPT, fetch_accept())
+            .send()
+            .await?
+            .error_for_status()?;
+        parse_document(response.json().await?)
+    }
+
+    /// Delivers an activity to the inbox with HTTP Signature.
+    pub async fn deliver<T: Serialize>(
+        &self,
+        inbox: &Url,
+        activity: &T,
+        signer: &ApSigner,
+    ) -> FederationResult<()> {
+        let body = serde_json::to_vec(activity)
+            .map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+        let headers = signer.signed_headers(&Method::POST, inbox, Some(&body))?;
+        self.client
+            .post(inbox.clone())
+            .headers(headers)
+            .header(CONTENT_TYPE, APPLICATION_ACTIVITY_JSON)
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Signing identity for outgoing requests.
+#[derive(Debug, Clone)]
+pub struct ApSigner {
+    key_id: String,
+    private_key: RsaPrivateKey,
+}
+
+impl ApSigner {
+    pub fn new(key_id: impl Into<String>, private_key: RsaPrivateKey) -> ApSigner {
+        ApSigner {
+            key_id: key_id.into(),
+            private_key,
+        }
+    }
+
+    /// Loads signer of the local user, whose key ID is `/users/{id}#main-key`.
+    pub async fn load_local_user(
+        config: &Config,
+        container: &Container,
+        user_id: &str,
+    ) -> FederationResult<ApSigner> {
+        let Some(private_key_pem) = container.user.fetch_private_key(user_id).await? else {
+            return Err(FederationError::InvalidObject(format!(
+                "local user {user_id} has no private key"
+            )));
+        };
+        let private_key = RsaPrivateKey::from_pkcs8_pem(&private_key_pem)
+            .map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+
+        let mut key_id = local_user_url(config, user_id);
+        key_id.set_fragment(Some("main-key"));
+
+        Ok(ApSigner::new(key_id, private_key))
+    }
+
+    /// Loads signer of the instance actor, whose key ID is `/actor#main-key`.
+    pub async fn load_instance(
+        config: &Config,
+        container: &Container,
+    ) -> FederationResult<ApSigner> {
+        let Some(private_key_pem) = container.instance.fetch_private_key().await? else {
+            return Err(FederationError::InvalidObject(
+                "instance actor has no private key".into(),
+            ));
+        };
+        let private_key = RsaPrivateKey::from_pkcs8_pem(&private_key_pem)
+            .map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+
+        let mut key_id = local_instance_actor_url(config);
+        key_id.set_fragment(Some("main-key"));
+
+        Ok(ApSigner::new(key_id, private_key))
+    }
+
+    /// Constructs `Host`, `Date`, `Digest` (if body present) and `Signature` headers.
+    fn signed_headers(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&[u8]>,
+    ) -> FederationResult<HeaderMap> {
+        let host = acct_origin_of(url)
+            .ok_or_else(|| FederationError::InvalidObject(format!("invalid URL {url}")))?;
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(HOST, header_value(&host)?);
+        headers.insert(DATE, header_value(&fmt_http_date(SystemTime::now()))?);
+        let mut signed_names = vec![REQUEST_TARGET.to_string(), "host".into(), "date".into()];
+        if let Some(body) = body {
+            headers.insert(DIGEST, header_value(&digest_header(body))?);
+            signed_names.push(DIGEST.into());
+        }
+
+        let signing = signing_string(&signed_names, method, &path, &headers)?;
+        let signature = SignatureHeader {
+            key_id: self.key_id.clone(),
+            headers: signed_names,
+            signature: sign(self.private_key.clone(), &signing),
+        };
+        headers.insert(SIGNATURE, header_value(&signature.to_header_value())?);
+
+        Ok(headers)
+    }
+}
+
+/// Resolves JSON-LD terms of fetched document and deserializes it.
+fn parse_document<T: DeserializeOwned>(document: JsonValue) -> FederationResult<T> {
+    serde_json::from_value(canonicalize(document))
+        .map_err(|e| FederationError::InvalidObject(e.to_string()))
+}
+
+fn fetch_accept() -> String {
+    format!("{APPLICATION_ACTIVITY_JSON}, {APPLICATION_LD_JSON_ACTIVITYSTREAMS}")
+}
+
+fn header_value(value: &str) -> FederationResult<HeaderValue> {
+    HeaderValue::from_str(value).map_err(|e| FederationError::InvalidObject(e.to_string()))
+}
@@ -0,0 +1,68 @@
+use super::{
+    client::{ApClient, ApSigner},
+    moderation::ensure_not_suspended,
+    FederationError, FederationResult,
+};
+
+use std::{collections::BTreeSet, time::Duration};
+
+use monaxia_data::config::Config;
+use monaxia_job::job::{Job, MxJob};
+use monaxia_queue::{job::Producer, retry::Backoff};
+use monaxia_repository::Container;
+use serde_json::Value as JsonValue;
+use url::Url;
+
+/// Maximum retry count of a delivery. Last retry takes place about 2 days later.
+const DELIVERY_MAX_RETRY: usize = 8;
+
+/// Backoff strategy of deliveries.
+const DELIVERY_BACKOFF: Backoff = Backoff::Exponential {
+    initial: Duration::from_secs(60),
+    factor: 3.0,
+};
+
+/// Enqueues delivery jobs of the activity, one for each distinct inbox.
+pub async fn enqueue_deliveries(
+    producer: &Producer<MxJob>,
+    actor_id: &str,
+    inboxes: impl IntoIterator<Item = String>,
+    activity: &JsonValue,
+) -> FederationResult<()> {
+    let activity = activity.to_string();
+    let inboxes: BTreeSet<_> = inboxes.into_iter().collect();
+    for inbox in inboxes {
+        let job = Job::Deliver {
+            actor_id: actor_id.to_string(),
+            inbox,
+            activity: activity.clone(),
+        };
+        producer
+            .enqueue(
+                MxJob::new_retrying(job, DELIVERY_MAX_RETRY, DELIVERY_BACKOFF),
+                None,
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Signs and delivers the serialized activity to the inbox.
+/// Fails with `FederationError::Suspended` if the inbox is on a suspended domain.
+pub async fn deliver_activity(
+    config: &Config,
+    container: &Container,
+    client: &ApClient,
+    actor_id: &str,
+    inbox: &str,
+    activity: &str,
+) -> FederationResult<()> {
+    let inbox = Url::parse(inbox).map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+    ensure_not_suspended(container, &inbox).await?;
+    let activity: JsonValue = serde_json::from_str(activity)
+        .map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+
+    let signer = ApSigner::load_local_user(config, container, actor_id).await?;
+    client.deliver(&inbox, &activity, &signer).await
+}
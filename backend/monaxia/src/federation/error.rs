@@ -0,0 +1,83 @@
+use super::signature::SignatureError;
+
+use std::result::Result as StdResult;
+
+use monaxia_queue::error::Error as QueueError;
+use monaxia_repository::RepoError;
+use reqwest::{Error as ReqwestError, StatusCode};
+use thiserror::Error as ThisError;
+
+pub type Result<T> = StdResult<T, Error>;
+
+/// Error type for federation operations.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("signature error: {0}")]
+    Signature(#[from] SignatureError),
+
+    #[error("request error: {0}")]
+    Request(#[from] ReqwestError),
+
+    #[error("repository error: {0}")]
+    Repository(#[from] RepoError),
+
+    #[error("queue error: {0}")]
+    Queue(#[from] QueueError),
+
+    #[error("invalid object: {0}")]
+    InvalidObject(String),
+
+    #[error("domain {0} is suspended")]
+    Suspended(String),
+}
+
+impl Error {
+    /// Checks whether the operation may succeed if retried.
+    /// Client errors (4xx) except 408 and 429 are permanent.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Request(e) => match e.status() {
+                Some(status) => {
+                    status.is_server_error()
+                        || status == StatusCode::REQUEST_TIMEOUT
+                        || status == StatusCode::TOO_MANY_REQUESTS
+                }
+                None => !e.is_builder() && !e.is_decode(),
+            },
+            Error::Repository(_) | Error::Queue(_) => true,
+            Error::Signature(_) | Error::InvalidObject(_) | Error::Suspended(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    use reqwest::{Response, StatusCode};
+
+    fn status_error(status: u16) -> Error {
+        let response = http::Response::builder()
+            .status(status)
+            .body("")
+            .expect("invalid response");
+        let expected = StatusCode::from_u16(status).expect("invalid status");
+        let err = Response::from(response)
+            .error_for_status()
+            .expect_err("must be error status");
+        assert_eq!(err.status(), Some(expected));
+        Error::Request(err)
+    }
+
+    #[test]
+    fn is_transient_classifies() {
+        for status in [400, 401, 403, 404, 410, 422] {
+            assert!(!status_error(status).is_transient(), "{status}");
+        }
+        for status in [408, 429, 500, 502, 503] {
+            assert!(status_error(status).is_transient(), "{status}");
+        }
+        assert!(!Error::Suspended("example.com".into()).is_transient());
+        assert!(!Error::InvalidObject("invalid".into()).is_transient());
+    }
+}
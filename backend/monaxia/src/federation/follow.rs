@@ -0,0 +1,271 @@
+use super::{
+    delivery::enqueue_deliveries,
+    local::{local_activity_url, local_user_url},
+    FederationError, FederationResult,
+};
+use crate::constant::ap::ACTIVITYSTREAMS_CONTEXT;
+
+use monaxia_data::{
+    activity::ActivityRegistration,
+    config::Config,
+    follow::{Follow, FollowRegistration, FollowState},
+    id::now_order58,
+    user::{LocalUser, RemoteUser},
+};
+use monaxia_job::job::MxJob;
+use monaxia_queue::job::Producer;
+use monaxia_repository::{repo::follow::FollowFind, Container};
+use serde_json::{json, Value as JsonValue};
+
+/// Sends Follow to the remote user and records pending relationship.
+pub async fn follow_remote_user(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    follower: &LocalUser,
+    followee: &RemoteUser,
+) -> FederationResult<()> {
+    let activity_id = now_order58();
+    let activity_url = local_activity_url(config, &activity_id);
+    let follow = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": activity_url,
+        "type": "Follow",
+        "actor": local_user_url(config, &follower.id),
+        "object": followee.uri,
+    });
+
+    container
+        .follow
+        .register_follow(FollowRegistration {
+            follower_id: follower.id.clone(),
+            followee_id: followee.id.clone(),
+            state: FollowState::Pending,
+            activity_id: activity_url.to_string(),
+        })
+        .await?;
+    send_activity(
+        container,
+        producer,
+        &follower.id,
+        activity_id,
+        follow,
+        followee,
+    )
+    .await
+}
+
+/// Sends Undo(Follow) to the remote user and removes the relationship.
+/// Returns `false` if the local user does not follow the remote user.
+pub async fn unfollow_remote_user(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    follower: &LocalUser,
+    followee: &RemoteUser,
+) -> FederationResult<bool> {
+    let Some(follow) = container
+        .follow
+        .find_follow(FollowFind::Pair {
+            follower_id: &follower.id,
+            followee_id: &followee.id,
+        })
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    let activity_id = now_order58();
+    let actor_url = local_user_url(config, &follower.id);
+    let undo = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &activity_id),
+        "type": "Undo",
+        "actor": actor_url,
+        "object": {
+            "id": follow.activity_id,
+            "type": "Follow",
+            "actor": actor_url,
+            "object": followee.uri,
+        },
+    });
+
+    container.follow.remove_follow(&follow.id).await?;
+    send_activity(
+        container,
+        producer,
+        &follower.id,
+        activity_id,
+        undo,
+        followee,
+    )
+    .await?;
+    Ok(true)
+}
+
+/// Records the relationship of received Follow and sends Accept for it.
+pub async fn accept_follow(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    followee: &LocalUser,
+    follower: &RemoteUser,
+    follow: &JsonValue,
+) -> FederationResult<()> {
+    let follow_id = follow
+        .get("id")
+        .and_then(|id| id.as_str())
+        .ok_or_else(|| FederationError::InvalidObject("Follow must have id".into()))?;
+
+    container
+        .follow
+        .register_follow(FollowRegistration {
+            follower_id: follower.id.clone(),
+            followee_id: followee.id.clone(),
+            state: FollowState::Accepted,
+            activity_id: follow_id.to_string(),
+        })
+        .await?;
+
+    let activity_id = now_order58();
+    let accept = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &activity_id),
+        "type": "Accept",
+        "actor": local_user_url(config, &followee.id),
+        "object": follow,
+    });
+    send_activity(
+        container,
+        producer,
+        &followee.id,
+        activity_id,
+        accept,
+        follower,
+    )
+    .await
+}
+
+/// Records the relationship of received Follow as pending without responding.
+/// Used for followers whose domain is silenced and for local users who approve
+/// followers manually, until approved or rejected
+/// by `approve_follow` or `reject_follow`.
+pub async fn hold_follow(
+    container: &Container,
+    followee: &LocalUser,
+    follower: &RemoteUser,
+    follow: &JsonValue,
+) -> FederationResult<()> {
+    let follow_id = follow
+        .get("id")
+        .and_then(|id| id.as_str())
+        .ok_or_else(|| FederationError::InvalidObject("Follow must have id".into()))?;
+
+    container
+        .follow
+        .register_follow(FollowRegistration {
+            follower_id: follower.id.clone(),
+            followee_id: followee.id.clone(),
+            state: FollowState::Pending,
+            activity_id: follow_id.to_string(),
+        })
+        .await?;
+    Ok(())
+}
+
+/// Accepts the pending follow request held by `hold_follow`.
+pub async fn approve_follow(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    followee: &LocalUser,
+    follower: &RemoteUser,
+    follow: &Follow,
+) -> FederationResult<()> {
+    container.follow.accept_follow(&follow.id).await?;
+    respond_follow(
+        config, container, producer, "Accept", followee, follower, follow,
+    )
+    .await
+}
+
+/// Rejects the pending follow request held by `hold_follow` and removes it.
+pub async fn reject_follow(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    followee: &LocalUser,
+    follower: &RemoteUser,
+    follow: &Follow,
+) -> FederationResult<()> {
+    container.follow.remove_follow(&follow.id).await?;
+    respond_follow(
+        config, container, producer, "Reject", followee, follower, follow,
+    )
+    .await
+}
+
+/// Sends Accept or Reject for the Follow, reconstructed from the stored relationship.
+async fn respond_follow(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    response_type: &str,
+    followee: &LocalUser,
+    follower: &RemoteUser,
+    follow: &Follow,
+) -> FederationResult<()> {
+    let activity_id = now_order58();
+    let followee_url = local_user_url(config, &followee.id);
+    let response = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &activity_id),
+        "type": response_type,
+        "actor": followee_url,
+        "object": {
+            "id": follow.activity_id,
+            "type": "Follow",
+            "actor": follower.uri,
+            "object": followee_url,
+        },
+    });
+    send_activity(
+        container,
+        producer,
+        &followee.id,
+        activity_id,
+        response,
+        follower,
+    )
+    .await
+}
+
+/// Addresses a non-public activity to the remote user, records it and enqueues
+/// delivery to their inbox.
+async fn send_activity(
+    container: &Container,
+    producer: &Producer<MxJob>,
+    user_id: &str,
+    activity_id: String,
+    mut activity: JsonValue,
+    recipient: &RemoteUser,
+) -> FederationResult<()> {
+    let inbox = recipient.inbox.clone().ok_or_else(|| {
+        FederationError::InvalidObject(format!("inbox of {} is unknown", recipient.uri))
+    })?;
+    let activity_type = activity["type"].as_str().unwrap_or_default().to_string();
+    activity["to"] = json!([recipient.uri]);
+
+    container
+        .activity
+        .register_activity(ActivityRegistration {
+            id: activity_id,
+            user_id: user_id.to_string(),
+            activity_type,
+            body: activity.clone(),
+            public: false,
+        })
+        .await?;
+
+    enqueue_deliveries(producer, user_id, [inbox], &activity).await
+}
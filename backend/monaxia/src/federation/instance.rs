@@ -0,0 +1,81 @@
+use super::{
+    client::{ApClient, ApSigner},
+    local::local_instance_actor_url,
+};
+use crate::constant::KEY_LENGTH;
+
+use anyhow::{bail, Result};
+use monaxia_data::{
+    ap::vocabulary::{Actor, ActorEndpoints, ActorKind, IriOrObject, PublicKey},
+    config::Config,
+    instance::{InstanceActor, InstanceActorRegistration},
+};
+use monaxia_repository::Container;
+use rand::prelude::*;
+use rsa::RsaPrivateKey;
+use tracing::info;
+
+/// Constructs Application object of the instance actor without `@context`.
+pub fn instance_actor(config: &Config, instance_actor: &InstanceActor) -> Actor {
+    let base_url = config.cached.server_base_url();
+    let actor_url = local_instance_actor_url(config);
+    let pubkey_id = {
+        let mut url = actor_url.clone();
+        url.set_fragment(Some("main-key"));
+        url
+    };
+    let shared_inbox = base_url.join("/inbox").expect("URL error");
+
+    let mut actor = Actor::new(
+        ActorKind::Application,
+        actor_url.clone(),
+        shared_inbox.clone(),
+    );
+    actor.preferred_username = Some(instance_username(config));
+    actor.url = Some(IriOrObject::Iri(base_url.clone()).into());
+    actor.endpoints = Some(ActorEndpoints {
+        shared_inbox: Some(shared_inbox),
+    });
+    actor.public_key = Some(PublicKey {
+        id: pubkey_id.to_string(),
+        owner: actor_url,
+        public_key_pem: instance_actor.public_key.clone(),
+    });
+    actor.manually_approves_followers = Some(true);
+    actor.discoverable = Some(false);
+    actor.published = Some(instance_actor.created_at);
+    actor
+}
+
+/// `preferredUsername` of the instance actor, which is the acct origin itself
+/// so that it never collides with local usernames.
+pub fn instance_username(config: &Config) -> String {
+    config.cached.acct_origin()
+}
+
+/// Generates the key pair of the instance actor unless it exists.
+pub async fn ensure_instance_actor(container: &Container) -> Result<InstanceActor> {
+    if let Some(instance_actor) = container.instance.find_instance_actor().await? {
+        return Ok(instance_actor);
+    }
+
+    info!("generating key pair of the instance actor...");
+    let private_key = RsaPrivateKey::new(&mut thread_rng(), KEY_LENGTH)?;
+    container
+        .instance
+        .register_instance_actor(InstanceActorRegistration { private_key })
+        .await?;
+
+    // another process may have registered it first, so always read back
+    let Some(instance_actor) = container.instance.find_instance_actor().await? else {
+        bail!("failed to register the instance actor");
+    };
+    Ok(instance_actor)
+}
+
+/// Constructs the client which signs fetches as the instance actor.
+pub async fn instance_client(config: &Config, container: &Container) -> Result<ApClient> {
+    ensure_instance_actor(container).await?;
+    let signer = ApSigner::load_instance(config, container).await?;
+    Ok(ApClient::new(config)?.with_signer(signer))
+}
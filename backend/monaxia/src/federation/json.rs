@@ -0,0 +1,15 @@
+use serde_json::Value as JsonValue;
+
+/// Extracts ID from a property which is either IRI string or embedded object.
+pub fn object_id(value: &JsonValue) -> Option<&str> {
+    match value {
+        JsonValue::String(id) => Some(id),
+        JsonValue::Object(object) => object.get("id").and_then(|id| id.as_str()),
+        _ => None,
+    }
+}
+
+/// Extracts `type` property of the object.
+pub fn object_type(value: &JsonValue) -> Option<&str> {
+    value.get("type").and_then(|t| t.as_str())
+}
@@ -0,0 +1,101 @@
+use monaxia_data::config::Config;
+use url::Url;
+
+/// URL of the local user actor.
+pub fn local_user_url(config: &Config, user_id: &str) -> Url {
+    config
+        .cached
+        .server_base_url()
+        .join(&format!("/users/{user_id}"))
+        .expect("URL error")
+}
+
+/// URL of the instance actor.
+pub fn local_instance_actor_url(config: &Config) -> Url {
+    config
+        .cached
+        .server_base_url()
+        .join("/actor")
+        .expect("URL error")
+}
+
+/// URL of the profile page of the local user, like `/@username`.
+pub fn local_profile_url(config: &Config, username: &str) -> Url {
+    config
+        .cached
+        .server_base_url()
+        .join(&format!("/@{username}"))
+        .expect("URL error")
+}
+
+/// URL of the page of a note posted by the local user, like `/@username/{note_id}`.
+pub fn local_note_page_url(config: &Config, username: &str, note_id: &str) -> Url {
+    config
+        .cached
+        .server_base_url()
+        .join(&format!("/@{username}/{note_id}"))
+        .expect("URL error")
+}
+
+/// URL of an activity performed by a local user.
+pub fn local_activity_url(config: &Config, activity_id: &str) -> Url {
+    config
+        .cached
+        .server_base_url()
+        .join(&format!("/activities/{activity_id}"))
+        .expect("URL error")
+}
+
+/// URL of a note posted by a local user.
+pub fn local_note_url(config: &Config, note_id: &str) -> Url {
+    config
+        .cached
+        .server_base_url()
+        .join(&format!("/notes/{note_id}"))
+        .expect("URL error")
+}
+
+/// Extracts local user ID from the actor URL.
+/// Returns `None` if the URL does not point a local user.
+pub fn local_user_id_of<'a>(config: &Config, url: &'a str) -> Option<&'a str> {
+    let users_url = config
+        .cached
+        .server_base_url()
+        .join("/users/")
+        .expect("URL error");
+    let user_id = url.strip_prefix(users_url.as_str())?;
+    if user_id.is_empty() || user_id.contains(['/', '?', '#']) {
+        return None;
+    }
+    Some(user_id)
+}
+
+/// Extracts username from the profile URL of the local user, like `/@username`.
+/// Returns `None` if the URL does not point a local profile.
+pub fn local_username_of<'a>(config: &Config, url: &'a str) -> Option<&'a str> {
+    let profiles_url = config
+        .cached
+        .server_base_url()
+        .join("/@")
+        .expect("URL error");
+    let username = url.strip_prefix(profiles_url.as_str())?;
+    if username.is_empty() || username.contains(['/', '?', '#']) {
+        return None;
+    }
+    Some(username)
+}
+
+/// Extracts local note ID from the note URL.
+/// Returns `None` if the URL does not point a local note.
+pub fn local_note_id_of<'a>(config: &Config, url: &'a str) -> Option<&'a str> {
+    let notes_url = config
+        .cached
+        .server_base_url()
+        .join("/notes/")
+        .expect("URL error");
+    let note_id = url.strip_prefix(notes_url.as_str())?;
+    if note_id.is_empty() || note_id.contains(['/', '?', '#']) {
+        return None;
+    }
+    Some(note_id)
+}
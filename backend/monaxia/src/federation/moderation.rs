@@ -0,0 +1,34 @@
+use super::{FederationError, FederationResult};
+
+use monaxia_data::{ap::acct_origin_of, domain::DomainPolicy};
+use monaxia_repository::Container;
+use url::Url;
+
+/// Finds the policy applied to the host of the URL.
+pub async fn policy_of(container: &Container, url: &Url) -> FederationResult<DomainPolicy> {
+    let domain = acct_origin_of(url)
+        .ok_or_else(|| FederationError::InvalidObject(format!("invalid URL {url}")))?;
+    let policy = container.domain.find_policy(&domain).await?;
+    Ok(policy)
+}
+
+/// Fails with `FederationError::Suspended` if the domain is suspended.
+pub async fn ensure_domain_not_suspended(
+    container: &Container,
+    domain: &str,
+) -> FederationResult<()> {
+    if container.domain.find_policy(domain).await? == DomainPolicy::Suspend {
+        return Err(FederationError::Suspended(domain.to_string()));
+    }
+    Ok(())
+}
+
+/// Fails with `FederationError::Suspended` if the host of the URL is suspended.
+pub async fn ensure_not_suspended(container: &Container, url: &Url) -> FederationResult<()> {
+    if policy_of(container, url).await? == DomainPolicy::Suspend {
+        return Err(FederationError::Suspended(
+            acct_origin_of(url).unwrap_or_default(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use super::{
+    actor::update_profile,
+    client::ApClient,
+    delivery::enqueue_deliveries,
+    follow::{follow_remote_user, unfollow_remote_user},
+    local::{local_activity_url, local_user_url},
+    resolver::{fetch_actor_document, register_actor},
+    FederationError, FederationResult,
+};
+use crate::constant::ap::ACTIVITYSTREAMS_CONTEXT;
+
+use monaxia_data::{
+    activity::ActivityRegistration,
+    config::Config,
+    id::now_order58,
+    user::{LocalUser, LocalUserProfile, RemoteUser},
+};
+use monaxia_job::job::MxJob;
+use monaxia_queue::job::Producer;
+use monaxia_repository::{
+    repo::{follow::FollowFind, user::UserFind},
+    Container,
+};
+use serde_json::json;
+use url::Url;
+
+/// Fetches the target actor of moving and checks that it lists the source
+/// in `alsoKnownAs`. The target is registered as a remote user if valid.
+pub async fn verify_move_target(
+    container: &Container,
+    client: &ApClient,
+    source_uri: &str,
+    target_uri: &Url,
+) -> FederationResult<RemoteUser> {
+    let target = fetch_actor_document(container, client, target_uri).await?;
+    if !target.is_also_known_as(source_uri) {
+        return Err(FederationError::InvalidObject(format!(
+            "{target_uri} does not list {source_uri} in alsoKnownAs"
+        )));
+    }
+    register_actor(container, target).await
+}
+
+/// Marks the local user as moved to the target, and sends Update(Person) and Move to followers.
+/// The target must list the local user in `alsoKnownAs` beforehand.
+pub async fn move_account(
+    config: &Config,
+    container: &Container,
+    client: &ApClient,
+    producer: &Producer<MxJob>,
+    local_user: &LocalUser,
+    target_uri: &Url,
+) -> FederationResult<LocalUser> {
+    let actor_url = local_user_url(config, &local_user.id);
+    let target = verify_move_target(container, client, actor_url.as_str(), target_uri).await?;
+
+    let profile = LocalUserProfile {
+        moved_to: Some(target.uri.clone()),
+        ..local_user.profile.clone()
+    };
+    let local_user = update_profile(config, container, producer, local_user, profile).await?;
+
+    let activity_id = now_order58();
+    let move_activity = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &activity_id),
+        "type": "Move",
+        "actor": actor_url,
+        "to": [format!("{actor_url}/followers")],
+        "object": actor_url,
+        "target": target.uri,
+    });
+    container
+        .activity
+        .register_activity(ActivityRegistration {
+            id: activity_id,
+            user_id: local_user.id.clone(),
+            activity_type: "Move".to_string(),
+            body: move_activity.clone(),
+            public: false,
+        })
+        .await?;
+
+    let inboxes = container
+        .follow
+        .fetch_follower_inboxes(&local_user.id)
+        .await?;
+    enqueue_deliveries(producer, &local_user.id, inboxes, &move_activity).await?;
+
+    Ok(local_user)
+}
+
+/// Transfers follows of local users from the moved remote user to the target.
+/// The target is followed before unfollowing the source, so that a failure leaves
+/// the source followed and the transfer can be retried.
+/// Returns the number of transferred local users.
+pub async fn transfer_follows(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    source: &RemoteUser,
+    target: &RemoteUser,
+) -> FederationResult<usize> {
+    let follower_ids = container
+        .follow
+        .fetch_local_follower_ids(&source.id)
+        .await?;
+
+    let mut transferred = 0;
+    for follower_id in follower_ids {
+        let Some(follower) = container
+            .user
+            .find_local_user(UserFind::UserId(&follower_id))
+            .await?
+        else {
+            continue;
+        };
+
+        let following = container
+            .follow
+            .find_follow(FollowFind::Pair {
+                follower_id: &follower.id,
+                followee_id: &target.id,
+            })
+            .await?;
+        if following.is_none() {
+            follow_remote_user(config, container, producer, &follower, target).await?;
+        }
+        unfollow_remote_user(config, container, producer, &follower, source).await?;
+        transferred += 1;
+    }
+    Ok(transferred)
+}
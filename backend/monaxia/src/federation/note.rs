@@ -0,0 +1,244 @@
+use super::{
+    delivery::enqueue_deliveries,
+    local::{local_activity_url, local_note_url, local_user_url},
+    FederationResult,
+};
+use crate::constant::ap::{ACTIVITYSTREAMS_CONTEXT, PUBLIC_COLLECTION};
+
+use monaxia_data::{
+    activity::ActivityRegistration,
+    config::Config,
+    follow::FollowState,
+    id::now_order58,
+    note::{Note, NoteRegistration, NoteTombstone, NoteVisibility},
+    user::RemoteUser,
+};
+use monaxia_job::job::MxJob;
+use monaxia_queue::job::Producer;
+use monaxia_repository::{repo::follow::FollowFind, Container};
+use serde_json::{json, Value as JsonValue};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// Posts a new note of the local user, records Create activity in the outbox
+/// and delivers it to followers. `content` must be sanitized HTML.
+pub async fn create_note(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    user_id: &str,
+    content: String,
+    in_reply_to: Option<String>,
+    visibility: NoteVisibility,
+) -> FederationResult<Note> {
+    let followers_url = format!("{}/followers", local_user_url(config, user_id));
+    let public = PUBLIC_COLLECTION.to_string();
+    let (to, cc) = match visibility {
+        NoteVisibility::Public => (vec![public], vec![followers_url]),
+        NoteVisibility::Unlisted => (vec![followers_url], vec![public]),
+        NoteVisibility::Followers => (vec![followers_url], vec![]),
+    };
+    let published = OffsetDateTime::now_utc()
+        .replace_nanosecond(0)
+        .expect("invalid time");
+
+    let note = Note {
+        id: now_order58(),
+        user_id: user_id.to_string(),
+        content,
+        published,
+        in_reply_to,
+        to,
+        cc,
+    };
+    container
+        .note
+        .register_note(NoteRegistration {
+            id: note.id.clone(),
+            user_id: note.user_id.clone(),
+            content: note.content.clone(),
+            published: note.published,
+            in_reply_to: note.in_reply_to.clone(),
+            to: note.to.clone(),
+            cc: note.cc.clone(),
+        })
+        .await?;
+
+    let activity_id = now_order58();
+    let create = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &activity_id),
+        "type": "Create",
+        "actor": local_user_url(config, user_id),
+        "published": format_datetime(&note.published),
+        "to": note.to,
+        "cc": note.cc,
+        "object": note_object(config, &note),
+    });
+    container
+        .activity
+        .register_activity(ActivityRegistration {
+            id: activity_id,
+            user_id: user_id.to_string(),
+            activity_type: "Create".to_string(),
+            body: create.clone(),
+            public: is_public(&note),
+        })
+        .await?;
+
+    let inboxes = container.follow.fetch_follower_inboxes(user_id).await?;
+    enqueue_deliveries(producer, user_id, inboxes, &create).await?;
+
+    Ok(note)
+}
+
+/// Replaces the note with a tombstone, removes its Create activity and reactions,
+/// and delivers Delete to followers.
+pub async fn delete_note(
+    config: &Config,
+    container: &Container,
+    producer: &Producer<MxJob>,
+    note: &Note,
+) -> FederationResult<()> {
+    let note_url = local_note_url(config, &note.id);
+    container
+        .note
+        .delete_note(&note.id, note_url.as_str())
+        .await?;
+
+    let activity_id = now_order58();
+    let delete = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &activity_id),
+        "type": "Delete",
+        "actor": local_user_url(config, &note.user_id),
+        "to": note.to,
+        "cc": note.cc,
+        "object": {
+            "id": note_url,
+            "type": "Tombstone",
+        },
+    });
+    container
+        .activity
+        .register_activity(ActivityRegistration {
+            id: activity_id,
+            user_id: note.user_id.clone(),
+            activity_type: "Delete".to_string(),
+            body: delete.clone(),
+            public: is_public(note),
+        })
+        .await?;
+
+    let inboxes = container
+        .follow
+        .fetch_follower_inboxes(&note.user_id)
+        .await?;
+    enqueue_deliveries(producer, &note.user_id, inboxes, &delete).await
+}
+
+/// Constructs ActivityStreams Tombstone object of the deleted note without `@context`.
+pub fn tombstone_object(config: &Config, tombstone: &NoteTombstone) -> JsonValue {
+    json!({
+        "id": local_note_url(config, &tombstone.id),
+        "type": "Tombstone",
+        "formerType": "Note",
+        "deleted": format_datetime(&tombstone.deleted),
+    })
+}
+
+/// Constructs ActivityStreams Note object without `@context`.
+pub fn note_object(config: &Config, note: &Note) -> JsonValue {
+    let mut object = json!({
+        "id": local_note_url(config, &note.id),
+        "type": "Note",
+        "attributedTo": local_user_url(config, &note.user_id),
+        "content": note.content,
+        "published": format_datetime(&note.published),
+        "to": note.to,
+        "cc": note.cc,
+    });
+    if let Some(in_reply_to) = &note.in_reply_to {
+        object["inReplyTo"] = in_reply_to.as_str().into();
+    }
+    object
+}
+
+/// Checks whether the note is addressed to the public.
+pub fn is_public(note: &Note) -> bool {
+    note.to
+        .iter()
+        .chain(&note.cc)
+        .any(|a| a == PUBLIC_COLLECTION)
+}
+
+/// Checks whether the remote user is addressed by the note directly or as a follower.
+pub async fn is_audience(
+    config: &Config,
+    container: &Container,
+    note: &Note,
+    remote_user: &RemoteUser,
+) -> FederationResult<bool> {
+    let addressees = note.to.iter().chain(&note.cc).map(|a| a.as_str());
+    is_addressed(config, container, &note.user_id, addressees, remote_user).await
+}
+
+/// Checks whether the remote user is in the addressees of an object by the local user,
+/// directly or as a follower.
+pub async fn is_addressed<'a>(
+    config: &Config,
+    container: &Container,
+    user_id: &str,
+    mut addressees: impl Iterator<Item = &'a str> + Clone,
+    remote_user: &RemoteUser,
+) -> FederationResult<bool> {
+    if addressees.clone().any(|a| a == remote_user.uri) {
+        return Ok(true);
+    }
+
+    let followers_url = format!("{}/followers", local_user_url(config, user_id));
+    if !addressees.any(|a| a == followers_url) {
+        return Ok(false);
+    }
+    let follow = container
+        .follow
+        .find_follow(FollowFind::Pair {
+            follower_id: &remote_user.id,
+            followee_id: user_id,
+        })
+        .await?;
+    Ok(follow.is_some_and(|f| f.state == FollowState::Accepted))
+}
+
+/// Converts plain text into HTML paragraphs.
+pub fn text_to_html(text: &str) -> String {
+    text.split("\n\n")
+        .map(|paragraph| format!("<p>{}</p>", escape_html(paragraph).replace('\n', "<br>")))
+        .collect()
+}
+
+/// Escapes special characters of HTML.
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn format_datetime(datetime: &OffsetDateTime) -> String {
+    datetime.format(&Rfc3339).expect("invalid datetime")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::text_to_html;
+
+    #[test]
+    fn text_converts_to_html() {
+        assert_eq!(text_to_html("hello"), "<p>hello</p>");
+        assert_eq!(
+            text_to_html("a<b>\nc\n\n\"d\" & 'e'"),
+            "<p>a&lt;b&gt;<br>c</p><p>&quot;d&quot; &amp; &#39;e&#39;</p>"
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use super::{
+    client::ApClient,
+    delivery::enqueue_deliveries,
+    local::{local_activity_url, local_note_id_of, local_user_url},
+    moderation::ensure_not_suspended,
+    resolver::resolve_actor,
+    FederationError, FederationResult,
+};
+use crate::constant::ap::{ACTIVITYSTREAMS_CONTEXT, PUBLIC_COLLECTION};
+
+use monaxia_data::{
+    activity::ActivityRegistration,
+    ap::vocabulary::Object,
+    config::Config,
+    id::now_order58,
+    reaction::{ReactionKind, ReactionRegistration},
+    user::LocalUser,
+};
+use monaxia_job::job::MxJob;
+use monaxia_queue::job::Producer;
+use monaxia_repository::{repo::reaction::ReactionFind, Container};
+use serde_json::{json, Value as JsonValue};
+use url::Url;
+
+/// Author of the reacted object.
+struct ObjectAuthor {
+    uri: Url,
+
+    /// Inbox of remote authors. Local authors have `None`.
+    inbox: Option<String>,
+}
+
+/// Sends Like or Announce of the object by the local user and records the reaction.
+/// Returns `false` if the local user has already reacted to the object.
+pub async fn react(
+    config: &Config,
+    container: &Container,
+    client: &ApClient,
+    producer: &Producer<MxJob>,
+    local_user: &LocalUser,
+    kind: ReactionKind,
+    object_uri: &Url,
+) -> FederationResult<bool> {
+    let reacted = container
+        .reaction
+        .find_reaction(
+            kind,
+            ReactionFind::Pair {
+                user_id: &local_user.id,
+                object_uri: object_uri.as_str(),
+            },
+        )
+        .await?;
+    if reacted.is_some() {
+        return Ok(false);
+    }
+
+    let author = resolve_author(config, container, client, object_uri).await?;
+    let (to, cc) = addressing(config, kind, &local_user.id, &author);
+    let activity_id = now_order58();
+    let activity_url = local_activity_url(config, &activity_id);
+    let activity = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": activity_url,
+        "type": kind.as_str(),
+        "actor": local_user_url(config, &local_user.id),
+        "to": to,
+        "cc": cc,
+        "object": object_uri,
+    });
+
+    container
+        .activity
+        .register_activity(ActivityRegistration {
+            id: activity_id,
+            user_id: local_user.id.clone(),
+            activity_type: kind.as_str().to_string(),
+            body: activity.clone(),
+            public: kind == ReactionKind::Announce,
+        })
+        .await?;
+    container
+        .reaction
+        .register_reaction(ReactionRegistration {
+            kind,
+            user_id: local_user.id.clone(),
+            object_uri: object_uri.to_string(),
+            activity_id: activity_url.to_string(),
+            author_uri: Some(author.uri.to_string()),
+            author_inbox: author.inbox.clone(),
+        })
+        .await?;
+
+    let inboxes = reaction_inboxes(container, kind, &local_user.id, author).await?;
+    enqueue_deliveries(producer, &local_user.id, inboxes, &activity).await?;
+    Ok(true)
+}
+
+/// Sends Undo of Like or Announce and removes the reaction.
+/// The Undo is delivered to the author recorded at reaction, so that it works
+/// even after the object is gone.
+/// Returns `false` if the local user has not reacted to the object.
+pub async fn unreact(
+    config: &Config,
+    container: &Container,
+    client: &ApClient,
+    producer: &Producer<MxJob>,
+    local_user: &LocalUser,
+    kind: ReactionKind,
+    object_uri: &Url,
+) -> FederationResult<bool> {
+    let Some(reaction) = container
+        .reaction
+        .find_reaction(
+            kind,
+            ReactionFind::Pair {
+                user_id: &local_user.id,
+                object_uri: object_uri.as_str(),
+            },
+        )
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    let author = match reaction.author_uri.as_deref().map(Url::parse) {
+        Some(Ok(uri)) => ObjectAuthor {
+            uri,
+            inbox: reaction.author_inbox.clone(),
+        },
+        _ => resolve_author(config, container, client, object_uri).await?,
+    };
+    let (to, cc) = addressing(config, kind, &local_user.id, &author);
+    let actor_url = local_user_url(config, &local_user.id);
+    let activity_id = now_order58();
+    let undo = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": local_activity_url(config, &activity_id),
+        "type": "Undo",
+        "actor": actor_url,
+        "to": to,
+        "cc": cc,
+        "object": {
+            "id": reaction.activity_id,
+            "type": kind.as_str(),
+            "actor": actor_url,
+            "object": object_uri,
+        },
+    });
+
+    container
+        .activity
+        .register_activity(ActivityRegistration {
+            id: activity_id,
+            user_id: local_user.id.clone(),
+            activity_type: "Undo".to_string(),
+            body: undo.clone(),
+            public: false,
+        })
+        .await?;
+    container
+        .reaction
+        .remove_reaction(kind, &reaction.id)
+        .await?;
+
+    let inboxes = reaction_inboxes(container, kind, &local_user.id, author).await?;
+    enqueue_deliveries(producer, &local_user.id, inboxes, &undo).await?;
+    Ok(true)
+}
+
+/// Constructs embedded collection of reactions, which exposes only the number.
+pub fn reaction_collection(collection_url: &Url, total_items: usize) -> JsonValue {
+    json!({
+        "id": collection_url,
+        "type": "Collection",
+        "totalItems": total_items,
+    })
+}
+
+/// Resolves the author of the object, which is either a local note or a remote object.
+async fn resolve_author(
+    config: &Config,
+    container: &Container,
+    client: &ApClient,
+    object_uri: &Url,
+) -> FederationResult<ObjectAuthor> {
+    if let Some(note_id) = local_note_id_of(config, object_uri.as_str()) {
+        let note =
+            container.note.find_note(note_id).await?.ok_or_else(|| {
+                FederationError::InvalidObject(format!("note {note_id} not found"))
+            })?;
+        return Ok(ObjectAuthor {
+            uri: local_user_url(config, &note.user_id),
+            inbox: None,
+        });
+    }
+
+    ensure_not_suspended(container, object_uri).await?;
+    let object = client.fetch::<Object>(object_uri).await?;
+    let author_uri = object
+        .attributed_to
+        .as_ref()
+        .and_then(|authors| authors.iter().find_map(|a| a.id()))
+        .cloned()
+        .ok_or_else(|| {
+            FederationError::InvalidObject(format!("object {object_uri} has no attributedTo"))
+        })?;
+    let author = resolve_actor(container, client, &author_uri).await?;
+    Ok(ObjectAuthor {
+        uri: author_uri,
+        inbox: author.inbox,
+    })
+}
+
+/// Likes are addressed to the author, while Announces are public.
+fn addressing(
+    config: &Config,
+    kind: ReactionKind,
+    user_id: &str,
+    author: &ObjectAuthor,
+) -> (Vec<String>, Vec<String>) {
+    let author_uri = author.uri.to_string();
+    match kind {
+        ReactionKind::Like => (vec![author_uri], vec![]),
+        ReactionKind::Announce => {
+            let followers_url = format!("{}/followers", local_user_url(config, user_id));
+            (
+                vec![PUBLIC_COLLECTION.to_string()],
+                vec![author_uri, followers_url],
+            )
+        }
+    }
+}
+
+/// Likes are delivered to the author, and Announces to followers as well.
+async fn reaction_inboxes(
+    container: &Container,
+    kind: ReactionKind,
+    user_id: &str,
+    author: ObjectAuthor,
+) -> FederationResult<Vec<String>> {
+    let mut inboxes: Vec<_> = author.inbox.into_iter().collect();
+    if kind == ReactionKind::Announce {
+        inboxes.extend(container.follow.fetch_follower_inboxes(user_id).await?);
+    }
+    Ok(inboxes)
+}
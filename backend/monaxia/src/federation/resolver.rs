@@ -0,0 +1,283 @@
+use super::{
+    client::ApClient,
+    moderation::{ensure_domain_not_suspended, ensure_not_suspended, policy_of},
+    signature::parse_public_key_pem,
+    webfinger::lookup_acct,
+    FederationError, FederationResult,
+};
+
+use monaxia_data::{
+    ap::{
+        acct_origin_of,
+        vocabulary::{Actor, AnyObject, IriOrObject, OneOrMany, PublicKey},
+        Acct,
+    },
+    domain::DomainPolicy,
+    user::{RemoteUser, RemoteUserRegistration},
+};
+use monaxia_repository::{repo::user::RemoteUserFind, Container};
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+use tracing::{info, warn};
+use url::Url;
+
+/// Interval to refresh known remote actors.
+const ACTOR_REFRESH_INTERVAL: Duration = Duration::days(1);
+
+/// Minimum interval to refetch the key owner on signature mismatch.
+const KEY_REFETCH_INTERVAL: Duration = Duration::minutes(1);
+
+/// Dereferenced `keyId`. Most implementations return the actor itself,
+/// but some return a standalone key object.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum KeyDocument {
+    Actor(Box<Actor>),
+    Key(PublicKey),
+}
+
+/// Finds the owner of the key from known remote users, or fetches it from remote.
+pub async fn resolve_key_owner(
+    container: &Container,
+    client: &ApClient,
+    key_id: &str,
+) -> FederationResult<RemoteUser> {
+    let key_url = Url::parse(key_id).map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+    ensure_not_suspended(container, &key_url).await?;
+
+    if let Some(remote_user) = container
+        .user
+        .find_remote_user(RemoteUserFind::KeyId(key_id))
+        .await?
+    {
+        return Ok(remote_user);
+    }
+
+    fetch_key_owner(container, client, key_id).await
+}
+
+/// Refetches the owner of the key which might have been rotated with the same ID.
+/// Returns `None` if the known owner has been fetched too recently.
+pub async fn refresh_key_owner(
+    container: &Container,
+    client: &ApClient,
+    known_owner: &RemoteUser,
+) -> FederationResult<Option<RemoteUser>> {
+    let refetch_after = known_owner
+        .fetched_at
+        .map(|f| f + KEY_REFETCH_INTERVAL)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+    if refetch_after > OffsetDateTime::now_utc() {
+        return Ok(None);
+    }
+
+    let key_url = Url::parse(&known_owner.key_id)
+        .map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+    ensure_not_suspended(container, &key_url).await?;
+    let remote_user = fetch_key_owner(container, client, &known_owner.key_id).await?;
+    Ok(Some(remote_user))
+}
+
+/// Dereferences the key and registers its owner.
+async fn fetch_key_owner(
+    container: &Container,
+    client: &ApClient,
+    key_id: &str,
+) -> FederationResult<RemoteUser> {
+    let key_url = Url::parse(key_id).map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+    let mut document_url = key_url.clone();
+    document_url.set_fragment(None);
+
+    let actor = match client.fetch::<KeyDocument>(&document_url).await? {
+        KeyDocument::Actor(actor) => *actor,
+        KeyDocument::Key(key) => {
+            ensure_not_suspended(container, &key.owner).await?;
+            client.fetch::<Actor>(&key.owner).await?
+        }
+    };
+    if actor.public_key.as_ref().map(|k| k.id.as_str()) != Some(key_id) {
+        return Err(FederationError::InvalidObject(format!(
+            "key {key_id} is not owned by {}",
+            actor.id
+        )));
+    }
+
+    register_actor(container, actor).await
+}
+
+/// Finds a remote user from known ones, or fetches the actor from remote.
+/// Known users are refreshed if they were fetched long ago.
+pub async fn resolve_actor(
+    container: &Container,
+    client: &ApClient,
+    actor_uri: &Url,
+) -> FederationResult<RemoteUser> {
+    ensure_not_suspended(container, actor_uri).await?;
+
+    let known_user = container
+        .user
+        .find_remote_user(RemoteUserFind::Uri(actor_uri.as_str()))
+        .await?;
+    let Some(known_user) = known_user else {
+        return fetch_actor(container, client, actor_uri).await;
+    };
+
+    let refresh_after = known_user
+        .fetched_at
+        .map(|f| f + ACTOR_REFRESH_INTERVAL)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+    if refresh_after > OffsetDateTime::now_utc() {
+        return Ok(known_user);
+    }
+    match fetch_actor(container, client, actor_uri).await {
+        Ok(remote_user) => Ok(remote_user),
+        Err(e) => {
+            warn!("failed to refresh {actor_uri}, using known one: {e}");
+            Ok(known_user)
+        }
+    }
+}
+
+/// Looks up the actor of the acct by WebFinger and resolves it.
+pub async fn resolve_acct(
+    container: &Container,
+    client: &ApClient,
+    acct: &Acct,
+) -> FederationResult<RemoteUser> {
+    ensure_domain_not_suspended(container, acct.origin()).await?;
+    let actor_uri = lookup_acct(client, acct).await?;
+    resolve_actor(container, client, &actor_uri).await
+}
+
+/// Fetches the actor from remote and registers or updates it as a remote user.
+pub async fn fetch_actor(
+    container: &Container,
+    client: &ApClient,
+    actor_uri: &Url,
+) -> FederationResult<RemoteUser> {
+    let actor = fetch_actor_document(container, client, actor_uri).await?;
+    register_actor(container, actor).await
+}
+
+/// Fetches the actor from remote without registering it.
+pub async fn fetch_actor_document(
+    container: &Container,
+    client: &ApClient,
+    actor_uri: &Url,
+) -> FederationResult<Actor> {
+    ensure_not_suspended(container, actor_uri).await?;
+
+    let actor = client.fetch::<Actor>(actor_uri).await?;
+    if actor.id.origin() != actor_uri.origin() {
+        return Err(FederationError::InvalidObject(format!(
+            "actor {} has different origin from {actor_uri}",
+            actor.id
+        )));
+    }
+    Ok(actor)
+}
+
+/// Verifies the actor and registers as a remote user.
+pub async fn register_actor(container: &Container, actor: Actor) -> FederationResult<RemoteUser> {
+    let (Some(username), Some(key)) = (actor.preferred_username, actor.public_key) else {
+        return Err(FederationError::InvalidObject(format!(
+            "actor {} must have preferredUsername and publicKey",
+            actor.id
+        )));
+    };
+    let key_id = key.id;
+    let key_url = Url::parse(&key_id).map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+    if key.owner != actor.id || key_url.origin() != actor.id.origin() {
+        return Err(FederationError::InvalidObject(format!(
+            "key {key_id} is not owned by {}",
+            actor.id
+        )));
+    }
+    if actor.inbox.origin() != actor.id.origin() {
+        return Err(FederationError::InvalidObject(format!(
+            "actor {} has inbox on different origin",
+            actor.id
+        )));
+    }
+
+    let shared_inbox = actor
+        .endpoints
+        .and_then(|e| e.shared_inbox)
+        .filter(|si| si.origin() == actor.id.origin());
+    let avatar_url = match policy_of(container, &actor.id).await? {
+        DomainPolicy::RejectMedia => None,
+        _ => actor.icon.as_ref().and_then(image_url),
+    };
+
+    let domain = acct_origin_of(&actor.id)
+        .ok_or_else(|| FederationError::InvalidObject(format!("invalid actor ID {}", actor.id)))?;
+    let public_key = parse_public_key_pem(&key.public_key_pem)?;
+
+    container.domain.acknowledge(&domain).await?;
+    container
+        .user
+        .register_remote_user(
+            RemoteUserRegistration {
+                username,
+                uri: actor.id.to_string(),
+                key_id: key_id.clone(),
+                inbox: actor.inbox.to_string(),
+                shared_inbox: shared_inbox.map(|si| si.to_string()),
+                display_name: actor.name,
+                summary: actor.summary,
+                avatar_url,
+                public_key,
+            },
+            &domain,
+        )
+        .await?;
+    info!("registered remote user {}", actor.id);
+
+    let remote_user = container
+        .user
+        .find_remote_user(RemoteUserFind::Uri(actor.id.as_str()))
+        .await?
+        .ok_or_else(|| FederationError::InvalidObject(format!("actor {} vanished", actor.id)))?;
+    Ok(remote_user)
+}
+
+/// Extracts URL from `icon` or `image` property, which may be Image, Link, or array of them.
+fn image_url(images: &OneOrMany<IriOrObject<AnyObject>>) -> Option<String> {
+    images.iter().find_map(|image| match image.as_object() {
+        None => image.id().map(|url| url.to_string()),
+        Some(AnyObject::Link(link)) => Some(link.href.to_string()),
+        Some(AnyObject::Object(object)) => object
+            .url
+            .as_ref()
+            .and_then(|urls| urls.iter().find_map(|url| url.id()))
+            .map(|url| url.to_string()),
+        Some(_) => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::image_url as image_url_typed;
+
+    use serde_json::{json, Value as JsonValue};
+
+    fn image_url(value: &JsonValue) -> Option<String> {
+        image_url_typed(&serde_json::from_value(value.clone()).expect("invalid image"))
+    }
+
+    #[test]
+    fn image_url_extracts() {
+        let url = "https://example.com/avatar.png";
+        assert_eq!(image_url(&json!(url)).as_deref(), Some(url));
+        assert_eq!(
+            image_url(&json!({ "type": "Image", "url": url })).as_deref(),
+            Some(url)
+        );
+        assert_eq!(
+            image_url(&json!([{ "type": "Image", "url": { "type": "Link", "href": url } }]))
+                .as_deref(),
+            Some(url)
+        );
+        assert_eq!(image_url(&json!({ "type": "Image" })), None);
+    }
+}
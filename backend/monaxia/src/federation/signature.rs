@@ -0,0 +1,325 @@
+use std::time::{Duration, SystemTime};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use http::{HeaderMap, Method};
+use httpdate::parse_http_date;
+use rsa::{
+    pkcs1::DecodeRsaPublicKey,
+    pkcs1v15::{Signature, SigningKey, VerifyingKey},
+    pkcs8::DecodePublicKey,
+    signature::{SignatureEncoding, Signer, Verifier},
+    RsaPrivateKey, RsaPublicKey,
+};
+use sha2::{Digest, Sha256};
+use thiserror::Error as ThisError;
+
+/// Pseudo header name for request target.
+pub const REQUEST_TARGET: &str = "(request-target)";
+
+/// Headers which must be signed in every request.
+pub const REQUIRED_HEADERS: [&str; 3] = [REQUEST_TARGET, "host", "date"];
+
+/// Algorithm name for outgoing signatures.
+const SIGNATURE_ALGORITHM: &str = "rsa-sha256";
+
+/// Allowed difference between `Date` header and current time.
+const CLOCK_SKEW: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, ThisError)]
+pub enum SignatureError {
+    #[error("signature header is malformed")]
+    Malformed,
+
+    #[error("parameter {0} is missing")]
+    MissingParameter(&'static str),
+
+    #[error("algorithm {0} is not supported")]
+    UnsupportedAlgorithm(String),
+
+    #[error("header {0} must be signed")]
+    UnsignedHeader(String),
+
+    #[error("header {0} is missing")]
+    MissingHeader(String),
+
+    #[error("date is out of acceptable range")]
+    DateOutOfRange,
+
+    #[error("digest does not match")]
+    DigestMismatch,
+
+    #[error("public key is invalid")]
+    InvalidKey,
+
+    #[error("signature verification failed")]
+    VerificationFailed,
+}
+
+/// Parsed `Signature` header (draft-cavage-http-signatures).
+#[derive(Debug, Clone)]
+pub struct SignatureHeader {
+    pub key_id: String,
+    pub headers: Vec<String>,
+    pub signature: Vec<u8>,
+}
+
+impl SignatureHeader {
+    pub fn parse(input: &str) -> Result<SignatureHeader, SignatureError> {
+        let mut key_id = None;
+        let mut algorithm = None;
+        let mut headers = None;
+        let mut signature = None;
+
+        let mut rest = input.trim();
+        while !rest.is_empty() {
+            let (name, after_name) = rest.split_once('=').ok_or(SignatureError::Malformed)?;
+            let after_quote = after_name
+                .strip_prefix('"')
+                .ok_or(SignatureError::Malformed)?;
+            let (value, after_value) = after_quote
+                .split_once('"')
+                .ok_or(SignatureError::Malformed)?;
+
+            match name.trim() {
+                "keyId" => key_id = Some(value.to_string()),
+                "algorithm" => algorithm = Some(value.to_string()),
+                "headers" => headers = Some(value.to_string()),
+                "signature" => signature = Some(value.to_string()),
+                _ => (),
+            }
+
+            let after_value = after_value.trim_start();
+            rest = match after_value.strip_prefix(',') {
+                Some(r) => r.trim_start(),
+                None if after_value.is_empty() => after_value,
+                None => return Err(SignatureError::Malformed),
+            };
+        }
+
+        let key_id = key_id.ok_or(SignatureError::MissingParameter("keyId"))?;
+        let signature = signature.ok_or(SignatureError::MissingParameter("signature"))?;
+        let signature = BASE64
+            .decode(signature)
+            .map_err(|_| SignatureError::Malformed)?;
+        // defaults to `date` only if omitted
+        let headers = headers
+            .as_deref()
+            .unwrap_or("date")
+            .split_ascii_whitespace()
+            .map(|h| h.to_ascii_lowercase())
+            .collect();
+
+        if let Some(algorithm) = algorithm {
+            // hs2019 defers algorithm to the key; only RSA keys are supported
+            if algorithm != SIGNATURE_ALGORITHM && algorithm != "hs2019" {
+                return Err(SignatureError::UnsupportedAlgorithm(algorithm));
+            }
+        }
+
+        Ok(SignatureHeader {
+            key_id,
+            headers,
+            signature,
+        })
+    }
+
+    /// Ensures that specified headers are included in signed headers.
+    pub fn ensure_signed<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), SignatureError> {
+        for name in names {
+            if !self.headers.iter().any(|h| h == name) {
+                return Err(SignatureError::UnsignedHeader(name.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes into `Signature` header value.
+    pub fn to_header_value(&self) -> String {
+        format!(
+            r#"keyId="{}",algorithm="{SIGNATURE_ALGORITHM}",headers="{}",signature="{}""#,
+            self.key_id,
+            self.headers.join(" "),
+            BASE64.encode(&self.signature),
+        )
+    }
+}
+
+/// Constructs the signing string from listed headers.
+/// `path` must contain query part if present.
+pub fn signing_string(
+    header_names: &[String],
+    method: &Method,
+    path: &str,
+    headers: &HeaderMap,
+) -> Result<String, SignatureError> {
+    let mut lines = Vec::with_capacity(header_names.len());
+    for name in header_names {
+        if name == REQUEST_TARGET {
+            let method = method.as_str().to_ascii_lowercase();
+            lines.push(format!("{REQUEST_TARGET}: {method} {path}"));
+            continue;
+        }
+
+        let values: Vec<_> = headers
+            .get_all(name.as_str())
+            .iter()
+            .map(|v| v.to_str().map(|s| s.trim()))
+            .collect::<Result<_, _>>()
+            .map_err(|_| SignatureError::MissingHeader(name.clone()))?;
+        if values.is_empty() {
+            return Err(SignatureError::MissingHeader(name.clone()));
+        }
+        lines.push(format!("{name}: {}", values.join(", ")));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Parses PEM-encoded RSA public key in SPKI or PKCS#1 format.
+pub fn parse_public_key_pem(pem: &str) -> Result<RsaPublicKey, SignatureError> {
+    RsaPublicKey::from_public_key_pem(pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+        .map_err(|_| SignatureError::InvalidKey)
+}
+
+/// Signs with RSASSA-PKCS1-v1_5 with SHA-256.
+pub fn sign(private_key: RsaPrivateKey, signing_string: &str) -> Vec<u8> {
+    let signing_key = SigningKey::<Sha256>::new(private_key);
+    signing_key.sign(signing_string.as_bytes()).to_vec()
+}
+
+/// Verifies RSASSA-PKCS1-v1_5 with SHA-256 signature.
+pub fn verify_signature(
+    public_key: RsaPublicKey,
+    signing_string: &str,
+    signature: &[u8],
+) -> Result<(), SignatureError> {
+    let verifying_key = VerifyingKey::<Sha256>::new(public_key);
+    let signature =
+        Signature::try_from(signature).map_err(|_| SignatureError::VerificationFailed)?;
+    verifying_key
+        .verify(signing_string.as_bytes(), &signature)
+        .map_err(|_| SignatureError::VerificationFailed)
+}
+
+/// Checks that `Date` header value is close enough to current time.
+pub fn verify_date(date: &str) -> Result<(), SignatureError> {
+    let date = parse_http_date(date).map_err(|_| SignatureError::DateOutOfRange)?;
+    let now = SystemTime::now();
+    let difference = match now.duration_since(date) {
+        Ok(d) => d,
+        Err(e) => e.duration(),
+    };
+    if difference > CLOCK_SKEW {
+        return Err(SignatureError::DateOutOfRange);
+    }
+    Ok(())
+}
+
+/// Constructs `Digest` header value of the body.
+pub fn digest_header(body: &[u8]) -> String {
+    format!("SHA-256={}", BASE64.encode(Sha256::digest(body)))
+}
+
+/// Checks that `Digest` header value contains matching SHA-256 digest.
+pub fn verify_digest(digest: &str, body: &[u8]) -> Result<(), SignatureError> {
+    let expected = BASE64.encode(Sha256::digest(body));
+    let matched = digest
+        .split(',')
+        .filter_map(|d| d.trim().split_once('='))
+        .any(|(algorithm, value)| algorithm.eq_ignore_ascii_case("SHA-256") && value == expected);
+    if !matched {
+        return Err(SignatureError::DigestMismatch);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        digest_header, sign, signing_string, verify_digest, verify_signature, SignatureHeader,
+        REQUEST_TARGET,
+    };
+
+    use http::{HeaderMap, HeaderValue, Method};
+    use rand::thread_rng;
+    use rsa::RsaPrivateKey;
+
+    #[test]
+    fn signature_header_parses() {
+        let header = SignatureHeader::parse(
+            r#"keyId="https://example.com/users/alice#main-key",algorithm="rsa-sha256",headers="(request-target) host date digest",signature="AAEC""#,
+        )
+        .expect("should parse");
+        assert_eq!(header.key_id, "https://example.com/users/alice#main-key");
+        assert_eq!(header.headers, [REQUEST_TARGET, "host", "date", "digest"]);
+        assert_eq!(header.signature, [0, 1, 2]);
+        assert!(header.ensure_signed(["host", "digest"]).is_ok());
+        assert!(header.ensure_signed(["content-type"]).is_err());
+
+        let defaulted =
+            SignatureHeader::parse(r#"keyId="a", signature="AAEC""#).expect("should parse");
+        assert_eq!(defaulted.headers, ["date"]);
+
+        assert!(SignatureHeader::parse(r#"keyId="a""#).is_err());
+        assert!(
+            SignatureHeader::parse(r#"keyId="a",signature="AAEC",algorithm="hmac-sha256""#)
+                .is_err()
+        );
+        assert!(SignatureHeader::parse(r#"keyId=a,signature="AAEC""#).is_err());
+    }
+
+    #[test]
+    fn signing_string_constructs() {
+        let mut headers = HeaderMap::new();
+        headers.insert("host", HeaderValue::from_static("example.com"));
+        headers.insert(
+            "date",
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+        let names = [REQUEST_TARGET, "host", "date"].map(String::from);
+
+        let signing = signing_string(&names, &Method::POST, "/users/1/inbox", &headers)
+            .expect("should construct");
+        assert_eq!(
+            signing,
+            "(request-target): post /users/1/inbox\nhost: example.com\ndate: Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+
+        let missing = [REQUEST_TARGET, "digest"].map(String::from);
+        assert!(signing_string(&missing, &Method::POST, "/", &headers).is_err());
+    }
+
+    #[test]
+    fn digest_verifies() {
+        let body = br#"{"type":"Follow"}"#;
+        let digest = "SHA-256=GYwYnH3BiO6aICFt0ThC5bUIJ4byvqdpWtR8m5fNkww=";
+        assert_eq!(digest_header(body), digest);
+        assert!(verify_digest(digest, body).is_ok());
+        assert!(verify_digest(&format!("MD5=AAAA, {digest}"), body).is_ok());
+        assert!(verify_digest(&digest.to_lowercase(), body).is_err());
+        assert!(verify_digest(digest, b"{}").is_err());
+    }
+
+    #[test]
+    fn signature_roundtrips() {
+        let private_key = RsaPrivateKey::new(&mut thread_rng(), 1024).expect("keygen failed");
+        let public_key = private_key.to_public_key();
+        let signing = "(request-target): get /users/1\nhost: example.com";
+
+        let header = SignatureHeader {
+            key_id: "https://example.com/users/1#main-key".into(),
+            headers: vec![REQUEST_TARGET.into(), "host".into()],
+            signature: sign(private_key, signing),
+        };
+        let parsed = SignatureHeader::parse(&header.to_header_value()).expect("should parse");
+        assert_eq!(parsed.key_id, header.key_id);
+        assert_eq!(parsed.headers, header.headers);
+
+        assert!(verify_signature(public_key.clone(), signing, &parsed.signature).is_ok());
+        assert!(verify_signature(public_key, "host: example.com", &parsed.signature).is_err());
+    }
+}
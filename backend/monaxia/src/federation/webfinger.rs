@@ -0,0 +1,224 @@
+use super::{client::ApClient, FederationError, FederationResult};
+use crate::constant::{
+    ap::ACTIVITYSTREAMS_CONTEXT,
+    mime::{
+        APPLICATION_ACTIVITY_JSON, APPLICATION_JRD_JSON, APPLICATION_LD_JSON, APPLICATION_XRD_XML,
+    },
+};
+
+use monaxia_data::ap::Acct;
+use serde::Deserialize;
+use tracing::debug;
+use url::{form_urlencoded::byte_serialize, Url};
+
+/// JSON resource descriptor returned by WebFinger.
+#[derive(Debug, Clone, Deserialize)]
+struct Jrd {
+    #[serde(default)]
+    links: Vec<JrdLink>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct JrdLink {
+    rel: String,
+
+    #[serde(rename = "type")]
+    link_type: Option<String>,
+
+    href: Option<Url>,
+}
+
+/// Looks up the actor URI of the acct by WebFinger.
+/// If WebFinger endpoint is unavailable, LRDD template in host-meta is used instead.
+pub async fn lookup_acct(client: &ApClient, acct: &Acct) -> FederationResult<Url> {
+    let resource = acct.to_subject();
+    let webfinger_url = Url::parse_with_params(
+        &format!("https://{}/.well-known/webfinger", acct.origin()),
+        [("resource", &resource)],
+    )
+    .map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+
+    let jrd = match client.fetch_jrd::<Jrd>(&webfinger_url).await {
+        Ok(Some(jrd)) => jrd,
+        Ok(None) => {
+            debug!(
+                "WebFinger endpoint of {} is missing, trying host-meta",
+                acct.origin()
+            );
+            lookup_by_host_meta(client, acct.origin(), &resource).await?
+        }
+        Err(e) if is_transport_error(&e) => {
+            debug!("WebFinger for {resource} failed, trying host-meta: {e}");
+            lookup_by_host_meta(client, acct.origin(), &resource).await?
+        }
+        Err(e) => return Err(e),
+    };
+
+    actor_link(&jrd).ok_or_else(|| {
+        FederationError::InvalidObject(format!("{resource} has no ActivityPub actor link"))
+    })
+}
+
+/// Fetches resource descriptor from the LRDD template in host-meta.
+/// The template may point to either JRD or XRD, so both are accepted.
+async fn lookup_by_host_meta(
+    client: &ApClient,
+    origin: &str,
+    resource: &str,
+) -> FederationResult<Jrd> {
+    let lrdd_url = lookup_lrdd(client, origin, resource).await?;
+    let descriptor = client
+        .fetch_text(
+            &lrdd_url,
+            &format!("{APPLICATION_JRD_JSON}, {APPLICATION_XRD_XML}"),
+        )
+        .await?;
+    parse_descriptor(&descriptor)
+}
+
+/// Constructs WebFinger URL from LRDD template in host-meta.
+async fn lookup_lrdd(client: &ApClient, origin: &str, resource: &str) -> FederationResult<Url> {
+    let host_meta_url = Url::parse(&format!("https://{origin}/.well-known/host-meta"))
+        .map_err(|e| FederationError::InvalidObject(e.to_string()))?;
+    let host_meta = client
+        .fetch_text(&host_meta_url, APPLICATION_XRD_XML)
+        .await?;
+
+    let template = lrdd_template(&host_meta).ok_or_else(|| {
+        FederationError::InvalidObject(format!("host-meta of {origin} has no LRDD template"))
+    })?;
+    let encoded_resource: String = byte_serialize(resource.as_bytes()).collect();
+    Url::parse(&template.replace("{uri}", &encoded_resource))
+        .map_err(|e| FederationError::InvalidObject(e.to_string()))
+}
+
+/// Checks whether the request failed before the server responded.
+fn is_transport_error(error: &FederationError) -> bool {
+    match error {
+        FederationError::Request(e) => e.status().is_none() && !e.is_builder() && !e.is_decode(),
+        _ => false,
+    }
+}
+
+/// Parses resource descriptor in either JRD or XRD.
+fn parse_descriptor(descriptor: &str) -> FederationResult<Jrd> {
+    if !descriptor.trim_start().starts_with('<') {
+        return serde_json::from_str(descriptor)
+            .map_err(|e| FederationError::InvalidObject(e.to_string()));
+    }
+
+    let links = xml_link_elements(descriptor)
+        .filter_map(|element| {
+            Some(JrdLink {
+                rel: xml_attribute(element, "rel")?.to_string(),
+                link_type: xml_attribute(element, "type").map(String::from),
+                href: xml_attribute(element, "href")
+                    .and_then(|href| Url::parse(&href.replace("&amp;", "&")).ok()),
+            })
+        })
+        .collect();
+    Ok(Jrd { links })
+}
+
+/// Finds `self` link to the ActivityPub actor.
+fn actor_link(jrd: &Jrd) -> Option<Url> {
+    jrd.links
+        .iter()
+        .filter(|l| l.rel == "self")
+        .find(|l| match l.link_type.as_deref() {
+            Some(APPLICATION_ACTIVITY_JSON) => true,
+            Some(t) => t.starts_with(APPLICATION_LD_JSON) && t.contains(ACTIVITYSTREAMS_CONTEXT),
+            None => false,
+        })
+        .and_then(|l| l.href.clone())
+}
+
+/// Extracts LRDD template from host-meta XRD document.
+fn lrdd_template(xrd: &str) -> Option<String> {
+    xml_link_elements(xrd)
+        .find(|element| xml_attribute(element, "rel") == Some("lrdd"))
+        .and_then(|element| xml_attribute(element, "template"))
+        .map(|template| template.replace("&amp;", "&"))
+}
+
+/// Iterates the inside of `Link` start tags in XRD document.
+fn xml_link_elements(xrd: &str) -> impl Iterator<Item = &str> {
+    xrd.split("<Link")
+        .skip(1)
+        .filter_map(|element| element.split('>').next())
+}
+
+/// Extracts attribute value from the inside of XML start tag.
+fn xml_attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=");
+    let mut rest = element;
+    while let Some(position) = rest.find(&pattern) {
+        let preceded_by_space = position == 0 || rest[..position].ends_with(char::is_whitespace);
+        let after = &rest[(position + pattern.len())..];
+        if preceded_by_space {
+            let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let value = &after[1..];
+            return value.find(quote).map(|end| &value[..end]);
+        }
+        rest = after;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{actor_link, lrdd_template, parse_descriptor, Jrd};
+
+    use serde_json::json;
+
+    #[test]
+    fn lrdd_template_extracts() {
+        let xrd = r#"<?xml version="1.0" encoding="UTF-8"?>
+<XRD xmlns="http://docs.oasis-open.org/ns/xri/xrd-1.0">
+  <Link rel="alternate" template="https://example.com/nope?{uri}"/>
+  <Link type='application/xrd+xml' rel='lrdd' template='https://example.com/.well-known/webfinger?format=xml&amp;resource={uri}'/>
+</XRD>"#;
+        assert_eq!(
+            lrdd_template(xrd).as_deref(),
+            Some("https://example.com/.well-known/webfinger?format=xml&resource={uri}")
+        );
+        assert_eq!(lrdd_template("<XRD></XRD>"), None);
+    }
+
+    #[test]
+    fn actor_link_selects() {
+        let jrd: Jrd = serde_json::from_value(json!({
+            "subject": "acct:alice@example.com",
+            "links": [
+                { "rel": "http://webfinger.net/rel/profile-page", "type": "text/html", "href": "https://example.com/@alice" },
+                { "rel": "self", "type": "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"", "href": "https://example.com/users/alice" },
+            ],
+        }))
+        .expect("invalid JRD");
+        assert_eq!(
+            actor_link(&jrd).map(String::from).as_deref(),
+            Some("https://example.com/users/alice")
+        );
+
+        let jrd: Jrd = serde_json::from_value(json!({
+            "links": [{ "rel": "self", "type": "text/html", "href": "https://example.com/alice" }],
+        }))
+        .expect("invalid JRD");
+        assert!(actor_link(&jrd).is_none());
+    }
+
+    #[test]
+    fn parse_descriptor_accepts_xrd() {
+        let xrd = r#"<?xml version="1.0" encoding="UTF-8"?>
+<XRD xmlns="http://docs.oasis-open.org/ns/xri/xrd-1.0">
+  <Subject>acct:alice@example.com</Subject>
+  <Link rel="http://webfinger.net/rel/profile-page" type="text/html" href="https://example.com/@alice"/>
+  <Link rel="self" type="application/activity+json" href="https://example.com/users/alice"/>
+</XRD>"#;
+        let jrd = parse_descriptor(xrd).expect("invalid XRD");
+        assert_eq!(
+            actor_link(&jrd).map(String::from).as_deref(),
+            Some("https://example.com/users/alice")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 mod cli;
 mod constant;
+mod federation;
 mod repository_impl;
 mod web;
 mod worker;
@@ -1,5 +1,10 @@
+mod activity;
 mod domain;
+mod follow;
+mod instance;
 mod migration;
+mod note;
+mod reaction;
 mod user;
 
 use anyhow::Result;
@@ -15,6 +20,11 @@ pub async fn construct_container(config: &Config) -> Result<Container> {
     Ok(Container {
         migration: Arc::new(migration::MigrationRepositoryImpl(pool.clone())),
         user: Arc::new(user::UserRepositoryImpl(pool.clone())),
-        domain: Arc::new(domain::DomainpositoryImpl(pool)),
+        domain: Arc::new(domain::DomainpositoryImpl(pool.clone())),
+        activity: Arc::new(activity::ActivityRepositoryImpl(pool.clone())),
+        follow: Arc::new(follow::FollowRepositoryImpl(pool.clone())),
+        note: Arc::new(note::NoteRepositoryImpl(pool.clone())),
+        instance: Arc::new(instance::InstanceRepositoryImpl(pool.clone())),
+        reaction: Arc::new(reaction::ReactionRepositoryImpl(pool)),
     })
 }
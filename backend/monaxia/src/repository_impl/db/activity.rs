@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use monaxia_data::{
+    activity::{Activity, ActivityRegistration},
+    pagination::Pagination,
+};
+use monaxia_db::activity::{
+    action::{
+        count_public_activities, fetch_public_activities, find_activity_by_id, register_activity,
+    },
+    schema::{Activity as ActivityRow, ActivityInsertion},
+};
+use monaxia_repository::{
+    repo::{activity::ActivityRepository, Repository},
+    RepoResult,
+};
+use sqlx::PgPool as Pool;
+
+pub struct ActivityRepositoryImpl(pub Pool);
+
+impl Repository for ActivityRepositoryImpl {}
+
+#[async_trait]
+impl ActivityRepository for ActivityRepositoryImpl {
+    async fn register_activity(&self, registration: ActivityRegistration) -> RepoResult<()> {
+        let mut conn = self.0.acquire().await?;
+        let insertion = ActivityInsertion {
+            id: registration.id,
+            user_id: registration.user_id,
+            activity_type: registration.activity_type,
+            body: registration.body,
+            public: registration.public,
+        };
+        register_activity(&mut conn, insertion).await?;
+        Ok(())
+    }
+
+    async fn find_activity(&self, activity_id: &str) -> RepoResult<Option<Activity>> {
+        let mut conn = self.0.acquire().await?;
+        let row = find_activity_by_id(&mut conn, activity_id).await?;
+        Ok(row.map(map_activity))
+    }
+
+    async fn count_outbox(&self, user_id: &str) -> RepoResult<usize> {
+        let mut conn = self.0.acquire().await?;
+        let count = count_public_activities(&mut conn, user_id).await?;
+        Ok(count)
+    }
+
+    async fn fetch_outbox(
+        &self,
+        user_id: &str,
+        pagination: &Pagination,
+    ) -> RepoResult<Vec<Activity>> {
+        let mut conn = self.0.acquire().await?;
+        let activities = fetch_public_activities(
+            &mut conn,
+            user_id,
+            pagination.max_id.as_deref(),
+            pagination.min_id.as_deref(),
+            pagination.limit as u64,
+        )
+        .await?;
+        Ok(activities.into_iter().map(map_activity).collect())
+    }
+}
+
+fn map_activity(row: ActivityRow) -> Activity {
+    Activity {
+        id: row.id,
+        user_id: row.user_id,
+        activity_type: row.activity_type,
+        body: row.body,
+        public: row.public,
+        published: row.published_at,
+    }
+}
@@ -1,8 +1,14 @@
 use async_trait::async_trait;
-use monaxia_db::domain::action::register_domain;
+use monaxia_data::domain::{covering_domains, DomainPolicy, ModeratedDomain};
+use monaxia_db::domain::{
+    action::{
+        fetch_moderated_domains, fetch_moderated_domains_in, register_domain, upsert_domain_policy,
+    },
+    schema::Domain as DomainRow,
+};
 use monaxia_repository::{
     repo::{domain::DomainRepository, Repository},
-    RepoResult,
+    RepoError, RepoResult,
 };
 use sqlx::PgPool as Pool;
 
@@ -17,4 +23,50 @@ impl DomainRepository for DomainpositoryImpl {
         let new_register = register_domain(&mut conn, domain).await?;
         Ok(new_register)
     }
+
+    async fn find_policy(&self, domain: &str) -> RepoResult<DomainPolicy> {
+        let mut conn = self.0.acquire().await?;
+        let candidates = covering_domains(domain);
+        let rows = fetch_moderated_domains_in(&mut conn, &candidates).await?;
+
+        let most_specific = candidates
+            .iter()
+            .find_map(|c| rows.iter().find(|r| r.domain == *c));
+        match most_specific {
+            Some(row) => parse_policy(&row.policy),
+            None => Ok(DomainPolicy::None),
+        }
+    }
+
+    async fn set_policy(
+        &self,
+        domain: &str,
+        policy: DomainPolicy,
+        reason: Option<&str>,
+    ) -> RepoResult<()> {
+        let mut conn = self.0.acquire().await?;
+        upsert_domain_policy(&mut conn, domain, policy.as_str(), reason).await?;
+        Ok(())
+    }
+
+    async fn fetch_moderated(&self) -> RepoResult<Vec<ModeratedDomain>> {
+        let mut conn = self.0.acquire().await?;
+        let rows = fetch_moderated_domains(&mut conn).await?;
+        rows.into_iter().map(map_moderated_domain).collect()
+    }
+}
+
+fn map_moderated_domain(row: DomainRow) -> RepoResult<ModeratedDomain> {
+    Ok(ModeratedDomain {
+        policy: parse_policy(&row.policy)?,
+        domain: row.domain,
+        reason: row.policy_reason,
+        recognized_at: row.recognized_at,
+    })
+}
+
+fn parse_policy(policy: &str) -> RepoResult<DomainPolicy> {
+    policy
+        .parse::<DomainPolicy>()
+        .map_err(|e| RepoError::Other(e.to_string()))
 }
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use monaxia_data::{
+    follow::{Follow, FollowRegistration, FollowRelated, FollowState},
+    id::now_order58,
+    pagination::Pagination,
+};
+use monaxia_db::follow::{
+    action::{
+        count_followees, count_followers, delete_follow, fetch_followees, fetch_follower_inboxes,
+        fetch_followers, fetch_local_follower_ids, fetch_pending_followers,
+        find_follow_by_activity_id, find_follow_by_pair, update_follow_state, upsert_follow,
+    },
+    schema::{Follow as FollowRow, FollowInsertion, FollowRelated as FollowRelatedRow},
+};
+use monaxia_repository::{
+    repo::{
+        follow::{FollowDirection, FollowFind, FollowRepository},
+        Repository,
+    },
+    RepoError, RepoResult,
+};
+use sqlx::PgPool as Pool;
+
+pub struct FollowRepositoryImpl(pub Pool);
+
+impl Repository for FollowRepositoryImpl {}
+
+#[async_trait]
+impl FollowRepository for FollowRepositoryImpl {
+    async fn register_follow(&self, registration: FollowRegistration) -> RepoResult<String> {
+        let mut conn = self.0.acquire().await?;
+        let insertion = FollowInsertion {
+            id: now_order58(),
+            follower_id: registration.follower_id,
+            followee_id: registration.followee_id,
+            state: registration.state.as_str().to_string(),
+            activity_id: registration.activity_id,
+        };
+        let id = upsert_follow(&mut conn, insertion).await?;
+        Ok(id)
+    }
+
+    async fn find_follow(&self, follow_find: FollowFind<'_>) -> RepoResult<Option<Follow>> {
+        let mut conn = self.0.acquire().await?;
+        let follow = match follow_find {
+            FollowFind::Pair {
+                follower_id,
+                followee_id,
+            } => find_follow_by_pair(&mut conn, follower_id, followee_id).await?,
+            FollowFind::ActivityId(activity_id) => {
+                find_follow_by_activity_id(&mut conn, activity_id).await?
+            }
+        };
+        follow.map(map_follow).transpose()
+    }
+
+    async fn accept_follow(&self, follow_id: &str) -> RepoResult<()> {
+        let mut conn = self.0.acquire().await?;
+        update_follow_state(&mut conn, follow_id, FollowState::Accepted.as_str()).await?;
+        Ok(())
+    }
+
+    async fn remove_follow(&self, follow_id: &str) -> RepoResult<()> {
+        let mut conn = self.0.acquire().await?;
+        delete_follow(&mut conn, follow_id).await?;
+        Ok(())
+    }
+
+    async fn fetch_follower_inboxes(&self, user_id: &str) -> RepoResult<Vec<String>> {
+        let mut conn = self.0.acquire().await?;
+        let inboxes = fetch_follower_inboxes(&mut conn, user_id).await?;
+        Ok(inboxes)
+    }
+
+    async fn fetch_local_follower_ids(&self, user_id: &str) -> RepoResult<Vec<String>> {
+        let mut conn = self.0.acquire().await?;
+        let follower_ids = fetch_local_follower_ids(&mut conn, user_id).await?;
+        Ok(follower_ids)
+    }
+
+    async fn fetch_follow_requests(&self, user_id: &str) -> RepoResult<Vec<FollowRelated>> {
+        let mut conn = self.0.acquire().await?;
+        let requests = fetch_pending_followers(&mut conn, user_id).await?;
+        Ok(requests.into_iter().map(map_follow_related).collect())
+    }
+
+    async fn count_related(&self, user_id: &str, direction: FollowDirection) -> RepoResult<usize> {
+        let mut conn = self.0.acquire().await?;
+        let count = match direction {
+            FollowDirection::Followers => count_followers(&mut conn, user_id).await?,
+            FollowDirection::Following => count_followees(&mut conn, user_id).await?,
+        };
+        Ok(count)
+    }
+
+    async fn fetch_related(
+        &self,
+        user_id: &str,
+        direction: FollowDirection,
+        pagination: &Pagination,
+    ) -> RepoResult<Vec<FollowRelated>> {
+        let mut conn = self.0.acquire().await?;
+        let max_id = pagination.max_id.as_deref();
+        let min_id = pagination.min_id.as_deref();
+        let limit = pagination.limit as u64;
+        let related = match direction {
+            FollowDirection::Followers => {
+                fetch_followers(&mut conn, user_id, max_id, min_id, limit).await?
+            }
+            FollowDirection::Following => {
+                fetch_followees(&mut conn, user_id, max_id, min_id, limit).await?
+            }
+        };
+        Ok(related.into_iter().map(map_follow_related).collect())
+    }
+}
+
+fn map_follow(row: FollowRow) -> RepoResult<Follow> {
+    let state = row
+        .state
+        .parse::<FollowState>()
+        .map_err(|e| RepoError::Other(e.to_string()))?;
+    Ok(Follow {
+        id: row.id,
+        follower_id: row.follower_id,
+        followee_id: row.followee_id,
+        state,
+        activity_id: row.activity_id,
+    })
+}
+
+fn map_follow_related(row: FollowRelatedRow) -> FollowRelated {
+    FollowRelated {
+        follow_id: row.id,
+        user_id: row.user_id,
+        uri: row.uri,
+    }
+}
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use monaxia_data::instance::{InstanceActor, InstanceActorRegistration};
+use monaxia_db::instance::{
+    action::{fetch_instance_private_key, find_instance_actor, register_instance_actor},
+    schema::InstanceActorInsertion,
+};
+use monaxia_repository::{
+    repo::{instance::InstanceRepository, Repository},
+    RepoResult,
+};
+use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+use sqlx::PgPool as Pool;
+
+pub struct InstanceRepositoryImpl(pub Pool);
+
+impl Repository for InstanceRepositoryImpl {}
+
+#[async_trait]
+impl InstanceRepository for InstanceRepositoryImpl {
+    async fn find_instance_actor(&self) -> RepoResult<Option<InstanceActor>> {
+        let mut conn = self.0.acquire().await?;
+        let row = find_instance_actor(&mut conn).await?;
+        Ok(row.map(|r| InstanceActor {
+            public_key: r.public_key,
+            created_at: r.created_at,
+        }))
+    }
+
+    async fn register_instance_actor(
+        &self,
+        registration: InstanceActorRegistration,
+    ) -> RepoResult<bool> {
+        let mut conn = self.0.acquire().await?;
+
+        let public_key = registration
+            .private_key
+            .to_public_key()
+            .to_public_key_pem(LineEnding::LF)
+            .expect("failed to write public key");
+        let private_key = registration
+            .private_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .expect("failed to write private key");
+        let insertion = InstanceActorInsertion {
+            public_key,
+            private_key: private_key.as_str(),
+        };
+        let registered = register_instance_actor(&mut conn, insertion).await?;
+        Ok(registered)
+    }
+
+    async fn fetch_private_key(&self) -> RepoResult<Option<String>> {
+        let mut conn = self.0.acquire().await?;
+        let private_key = fetch_instance_private_key(&mut conn).await?;
+        Ok(private_key)
+    }
+}
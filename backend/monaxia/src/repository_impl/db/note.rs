@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use monaxia_data::note::{Note, NoteRegistration, NoteTombstone};
+use monaxia_db::{
+    activity::action::delete_create_activities,
+    note::{
+        action::{find_note_by_id, find_tombstone_by_id, register_note, tombstone_note},
+        schema::NoteInsertion,
+    },
+    reaction::{action::delete_reactions_to, schema::ReactionTable},
+};
+use monaxia_repository::{
+    repo::{note::NoteRepository, Repository},
+    RepoError, RepoResult,
+};
+use serde_json::Value as JsonValue;
+use sqlx::{Acquire, PgPool as Pool};
+
+pub struct NoteRepositoryImpl(pub Pool);
+
+impl Repository for NoteRepositoryImpl {}
+
+#[async_trait]
+impl NoteRepository for NoteRepositoryImpl {
+    async fn register_note(&self, registration: NoteRegistration) -> RepoResult<()> {
+        let mut conn = self.0.acquire().await?;
+        let insertion = NoteInsertion {
+            id: registration.id,
+            user_id: registration.user_id,
+            content: registration.content,
+            published_at: registration.published,
+            in_reply_to: registration.in_reply_to,
+            to: registration.to.into(),
+            cc: registration.cc.into(),
+        };
+        register_note(&mut conn, insertion).await?;
+        Ok(())
+    }
+
+    async fn find_note(&self, note_id: &str) -> RepoResult<Option<Note>> {
+        let mut conn = self.0.acquire().await?;
+        let Some(row) = find_note_by_id(&mut conn, note_id).await? else {
+            return Ok(None);
+        };
+        Ok(Some(Note {
+            id: row.id,
+            user_id: row.user_id,
+            content: row.content,
+            published: row.published_at,
+            in_reply_to: row.in_reply_to,
+            to: decode_addressing(row.to)?,
+            cc: decode_addressing(row.cc)?,
+        }))
+    }
+
+    async fn delete_note(&self, note_id: &str, note_uri: &str) -> RepoResult<bool> {
+        let mut tx = self.0.begin().await?;
+        let conn = tx.acquire().await?;
+        let deleted = tombstone_note(&mut *conn, note_id).await?;
+        delete_create_activities(&mut *conn, note_uri).await?;
+        for table in [ReactionTable::Likes, ReactionTable::Announces] {
+            delete_reactions_to(&mut *conn, table, note_uri).await?;
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    async fn find_tombstone(&self, note_id: &str) -> RepoResult<Option<NoteTombstone>> {
+        let mut conn = self.0.acquire().await?;
+        let tombstone = find_tombstone_by_id(&mut conn, note_id).await?;
+        Ok(tombstone.map(|t| NoteTombstone {
+            id: t.id,
+            deleted: t.deleted_at,
+        }))
+    }
+}
+
+fn decode_addressing(value: JsonValue) -> RepoResult<Vec<String>> {
+    serde_json::from_value(value).map_err(|e| RepoError::Other(e.to_string()))
+}
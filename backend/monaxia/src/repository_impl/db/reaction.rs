@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use monaxia_data::{
+    id::now_order58,
+    reaction::{Reaction, ReactionKind, ReactionRegistration},
+};
+use monaxia_db::reaction::{
+    action::{
+        count_reactions, delete_reaction, delete_reactions_to, find_reaction_by_activity_id,
+        find_reaction_by_pair, insert_reaction,
+    },
+    schema::{Reaction as ReactionRow, ReactionInsertion, ReactionTable},
+};
+use monaxia_repository::{
+    repo::{
+        reaction::{ReactionFind, ReactionRepository},
+        Repository,
+    },
+    RepoResult,
+};
+use sqlx::PgPool as Pool;
+
+pub struct ReactionRepositoryImpl(pub Pool);
+
+impl Repository for ReactionRepositoryImpl {}
+
+#[async_trait]
+impl ReactionRepository for ReactionRepositoryImpl {
+    async fn register_reaction(&self, registration: ReactionRegistration) -> RepoResult<bool> {
+        let mut conn = self.0.acquire().await?;
+        let insertion = ReactionInsertion {
+            id: now_order58(),
+            user_id: registration.user_id,
+            object_uri: registration.object_uri,
+            activity_id: registration.activity_id,
+            author_uri: registration.author_uri,
+            author_inbox: registration.author_inbox,
+        };
+        let registered = insert_reaction(&mut conn, table_of(registration.kind), insertion).await?;
+        Ok(registered)
+    }
+
+    async fn find_reaction(
+        &self,
+        kind: ReactionKind,
+        reaction_find: ReactionFind<'_>,
+    ) -> RepoResult<Option<Reaction>> {
+        let mut conn = self.0.acquire().await?;
+        let table = table_of(kind);
+        let reaction = match reaction_find {
+            ReactionFind::Pair {
+                user_id,
+                object_uri,
+            } => find_reaction_by_pair(&mut conn, table, user_id, object_uri).await?,
+            ReactionFind::ActivityId(activity_id) => {
+                find_reaction_by_activity_id(&mut conn, table, activity_id).await?
+            }
+        };
+        Ok(reaction.map(|r| map_reaction(kind, r)))
+    }
+
+    async fn remove_reaction(&self, kind: ReactionKind, reaction_id: &str) -> RepoResult<()> {
+        let mut conn = self.0.acquire().await?;
+        delete_reaction(&mut conn, table_of(kind), reaction_id).await?;
+        Ok(())
+    }
+
+    async fn remove_reactions_to(&self, object_uri: &str) -> RepoResult<()> {
+        let mut tx = self.0.begin().await?;
+        for kind in [ReactionKind::Like, ReactionKind::Announce] {
+            delete_reactions_to(&mut tx, table_of(kind), object_uri).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn count_reactions(&self, kind: ReactionKind, object_uri: &str) -> RepoResult<usize> {
+        let mut conn = self.0.acquire().await?;
+        let count = count_reactions(&mut conn, table_of(kind), object_uri).await?;
+        Ok(count)
+    }
+}
+
+fn table_of(kind: ReactionKind) -> ReactionTable {
+    match kind {
+        ReactionKind::Like => ReactionTable::Likes,
+        ReactionKind::Announce => ReactionTable::Announces,
+    }
+}
+
+fn map_reaction(kind: ReactionKind, row: ReactionRow) -> Reaction {
+    Reaction {
+        id: row.id,
+        kind,
+        user_id: row.user_id,
+        object_uri: row.object_uri,
+        activity_id: row.activity_id,
+        author_uri: row.author_uri,
+        author_inbox: row.author_inbox,
+        created_at: row.created_at,
+    }
+}
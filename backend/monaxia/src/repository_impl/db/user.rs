@@ -1,24 +1,37 @@
 use async_trait::async_trait;
 use monaxia_data::{
     id::now_order58,
-    user::{LocalUser, LocalUserRegistration, RemoteUserRegistration},
+    user::{
+        LocalUser, LocalUserKeyRotation, LocalUserProfile, LocalUserRegistration, RemoteUser,
+        RemoteUserRegistration,
+    },
 };
-use monaxia_db::user::{
-    action::{
-        fetch_local_users_count, find_local_user_by_id, find_local_user_by_username,
-        local_user_occupied, register_local_user, register_user,
+use monaxia_db::{
+    note::action::tombstone_user_notes,
+    reaction::{action::delete_reactions_to_notes_of, schema::ReactionTable},
+    user::{
+        action::{
+            delete_user, fetch_local_user_private_key, fetch_local_users, fetch_local_users_count,
+            fetch_remote_inboxes, find_local_user_by_id, find_local_user_by_username,
+            find_remote_user_by_key_id, find_remote_user_by_uri, local_user_occupied,
+            register_local_user, register_user, update_local_user_private_key,
+            update_local_user_profile, update_user_public_key, upsert_remote_user,
+        },
+        schema::{
+            LocalUser as LocalUserRow, LocalUserInsertion, LocalUserProfileUpdate, UserInsertion,
+        },
     },
-    schema::{LocalUserInsertion, UserInsertion},
 };
 use monaxia_repository::{
     repo::{
-        user::{UserFind, UserRepository},
+        user::{RemoteUserFind, UserFind, UserRepository},
         Repository,
     },
-    RepoResult,
+    RepoError, RepoResult,
 };
 use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
 use sqlx::{Acquire, PgPool as Pool};
+use time::OffsetDateTime;
 
 pub struct UserRepositoryImpl(pub Pool);
 
@@ -57,6 +70,14 @@ impl UserRepository for UserRepositoryImpl {
             username: registration.username.clone(),
             domain: domain.to_string(),
             public_key,
+            uri: None,
+            key_id: None,
+            inbox: None,
+            shared_inbox: None,
+            display_name: None,
+            description: None,
+            avatar_url: None,
+            fetched_at: None,
         };
         register_user(&mut *conn, insertion).await?;
 
@@ -80,22 +101,31 @@ impl UserRepository for UserRepositoryImpl {
         registration: RemoteUserRegistration,
         domain: &str,
     ) -> RepoResult<String> {
-        let mut conn = self.0.acquire().await?;
+        let mut tx = self.0.begin().await?;
+        let conn = tx.acquire().await?;
 
-        let id = now_order58();
         let public_key = registration
             .public_key
             .to_public_key_pem(LineEnding::LF)
             .expect("failed to write public key");
         let insertion = UserInsertion {
-            id: id.clone(),
-            username: registration.username.clone(),
+            id: now_order58(),
+            username: registration.username,
             domain: domain.to_string(),
             public_key,
+            uri: Some(registration.uri),
+            key_id: Some(registration.key_id),
+            inbox: Some(registration.inbox),
+            shared_inbox: registration.shared_inbox,
+            display_name: registration.display_name,
+            description: registration.summary,
+            avatar_url: registration.avatar_url,
+            fetched_at: Some(OffsetDateTime::now_utc()),
         };
-        register_user(&mut conn, insertion).await?;
+        let id = upsert_remote_user(conn, insertion).await?;
+        tx.commit().await?;
 
-        todo!();
+        Ok(id)
     }
 
     async fn find_local_user(&self, user_find: UserFind<'_>) -> RepoResult<Option<LocalUser>> {
@@ -104,11 +134,130 @@ impl UserRepository for UserRepositoryImpl {
             UserFind::Username(un) => find_local_user_by_username(&mut conn, un).await?,
             UserFind::UserId(id) => find_local_user_by_id(&mut conn, id).await?,
         };
-        Ok(user.map(|u| LocalUser {
+        user.map(map_local_user).transpose()
+    }
+
+    async fn fetch_local_users(&self) -> RepoResult<Vec<LocalUser>> {
+        let mut conn = self.0.acquire().await?;
+        let users = fetch_local_users(&mut conn).await?;
+        users.into_iter().map(map_local_user).collect()
+    }
+
+    async fn update_local_profile(
+        &self,
+        user_id: &str,
+        profile: LocalUserProfile,
+    ) -> RepoResult<()> {
+        let mut conn = self.0.acquire().await?;
+        let fields =
+            serde_json::to_value(profile.fields).map_err(|e| RepoError::Other(e.to_string()))?;
+        let also_known_as = serde_json::to_value(profile.also_known_as)
+            .map_err(|e| RepoError::Other(e.to_string()))?;
+        let update = LocalUserProfileUpdate {
+            display_name: profile.display_name,
+            description: profile.summary,
+            avatar_url: profile.avatar_url,
+            header_url: profile.header_url,
+            manually_approves_followers: profile.manually_approves_followers,
+            profile_fields: fields,
+            also_known_as,
+            moved_to: profile.moved_to,
+        };
+        update_local_user_profile(&mut conn, user_id, update).await?;
+        Ok(())
+    }
+
+    async fn rotate_key(&self, user_id: &str, rotation: LocalUserKeyRotation) -> RepoResult<()> {
+        let mut tx = self.0.begin().await?;
+        let conn = tx.acquire().await?;
+
+        let public_key = rotation
+            .private_key
+            .to_public_key()
+            .to_public_key_pem(LineEnding::LF)
+            .expect("failed to write public key");
+        let private_key = rotation
+            .private_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .expect("failed to write private key");
+        update_user_public_key(&mut *conn, user_id, &public_key).await?;
+        update_local_user_private_key(&mut *conn, user_id, private_key.as_str()).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn fetch_private_key(&self, user_id: &str) -> RepoResult<Option<String>> {
+        let mut conn = self.0.acquire().await?;
+        let private_key = fetch_local_user_private_key(&mut conn, user_id).await?;
+        Ok(private_key)
+    }
+
+    async fn remove_user(&self, user_id: &str, notes_url: &str) -> RepoResult<()> {
+        let mut tx = self.0.begin().await?;
+        let conn = tx.acquire().await?;
+        for table in [ReactionTable::Likes, ReactionTable::Announces] {
+            delete_reactions_to_notes_of(&mut *conn, table, user_id, notes_url).await?;
+        }
+        tombstone_user_notes(&mut *conn, user_id).await?;
+        delete_user(&mut *conn, user_id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn fetch_remote_inboxes(&self) -> RepoResult<Vec<String>> {
+        let mut conn = self.0.acquire().await?;
+        let inboxes = fetch_remote_inboxes(&mut conn).await?;
+        Ok(inboxes)
+    }
+
+    async fn find_remote_user(
+        &self,
+        remote_user_find: RemoteUserFind<'_>,
+    ) -> RepoResult<Option<RemoteUser>> {
+        let mut conn = self.0.acquire().await?;
+        let user = match remote_user_find {
+            RemoteUserFind::Uri(uri) => find_remote_user_by_uri(&mut conn, uri).await?,
+            RemoteUserFind::KeyId(key_id) => find_remote_user_by_key_id(&mut conn, key_id).await?,
+        };
+        Ok(user.map(|u| RemoteUser {
             id: u.id,
             id_seq: u.id_seq.to_string(),
             username: u.username,
+            domain: u.domain,
+            uri: u.uri,
+            key_id: u.key_id,
+            inbox: u.inbox,
+            shared_inbox: u.shared_inbox,
+            display_name: u.display_name,
+            summary: u.description,
+            avatar_url: u.avatar_url,
             public_key: u.public_key,
+            fetched_at: u.fetched_at,
         }))
     }
 }
+
+fn map_local_user(user: LocalUserRow) -> RepoResult<LocalUser> {
+    let fields =
+        serde_json::from_value(user.profile_fields).map_err(|e| RepoError::Other(e.to_string()))?;
+    let also_known_as =
+        serde_json::from_value(user.also_known_as).map_err(|e| RepoError::Other(e.to_string()))?;
+    Ok(LocalUser {
+        id: user.id,
+        id_seq: user.id_seq.to_string(),
+        username: user.username,
+        public_key: user.public_key,
+        profile: LocalUserProfile {
+            display_name: user.display_name,
+            summary: user.description,
+            avatar_url: user.avatar_url,
+            header_url: user.header_url,
+            manually_approves_followers: user.manually_approves_followers,
+            fields,
+            also_known_as,
+            moved_to: user.moved_to,
+        },
+        created_at: user.created_at,
+    })
+}
@@ -1,5 +1,10 @@
+mod activity;
 mod domain;
+mod follow;
+mod instance;
 mod migration;
+mod note;
+mod reaction;
 mod user;
 
 use monaxia_repository::Container;
@@ -11,5 +16,10 @@ pub fn construct_container() -> Container {
         migration: Arc::new(migration::MigrationRepositoryImpl),
         user: Arc::new(user::UserRepositoryImpl),
         domain: Arc::new(domain::DomainpositoryImpl),
+        activity: Arc::new(activity::ActivityRepositoryImpl),
+        follow: Arc::new(follow::FollowRepositoryImpl),
+        note: Arc::new(note::NoteRepositoryImpl),
+        instance: Arc::new(instance::InstanceRepositoryImpl),
+        reaction: Arc::new(reaction::ReactionRepositoryImpl),
     }
 }
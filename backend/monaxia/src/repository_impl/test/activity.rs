@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use monaxia_data::{
+    activity::{Activity, ActivityRegistration},
+    pagination::Pagination,
+};
+use monaxia_repository::{
+    repo::{activity::ActivityRepository, Repository},
+    RepoResult,
+};
+
+pub struct ActivityRepositoryImpl;
+
+impl Repository for ActivityRepositoryImpl {}
+
+#[async_trait]
+impl ActivityRepository for ActivityRepositoryImpl {
+    async fn register_activity(&self, _registration: ActivityRegistration) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn find_activity(&self, _activity_id: &str) -> RepoResult<Option<Activity>> {
+        Ok(None)
+    }
+
+    async fn count_outbox(&self, _user_id: &str) -> RepoResult<usize> {
+        Ok(0)
+    }
+
+    async fn fetch_outbox(
+        &self,
+        _user_id: &str,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<Activity>> {
+        Ok(vec![])
+    }
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use monaxia_data::domain::{DomainPolicy, ModeratedDomain};
 use monaxia_repository::{
     repo::{domain::DomainRepository, Repository},
     RepoResult,
@@ -13,4 +14,21 @@ impl DomainRepository for DomainpositoryImpl {
     async fn acknowledge(&self, _domain: &str) -> RepoResult<bool> {
         Ok(true)
     }
+
+    async fn find_policy(&self, _domain: &str) -> RepoResult<DomainPolicy> {
+        Ok(DomainPolicy::None)
+    }
+
+    async fn set_policy(
+        &self,
+        _domain: &str,
+        _policy: DomainPolicy,
+        _reason: Option<&str>,
+    ) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn fetch_moderated(&self) -> RepoResult<Vec<ModeratedDomain>> {
+        Ok(vec![])
+    }
 }
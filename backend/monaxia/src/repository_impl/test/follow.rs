@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use monaxia_data::{
+    follow::{Follow, FollowRegistration, FollowRelated},
+    pagination::Pagination,
+};
+use monaxia_repository::{
+    repo::{
+        follow::{FollowDirection, FollowFind, FollowRepository},
+        Repository,
+    },
+    RepoResult,
+};
+
+pub struct FollowRepositoryImpl;
+
+impl Repository for FollowRepositoryImpl {}
+
+#[async_trait]
+impl FollowRepository for FollowRepositoryImpl {
+    async fn register_follow(&self, _registration: FollowRegistration) -> RepoResult<String> {
+        Ok("follow".to_string())
+    }
+
+    async fn find_follow(&self, _follow_find: FollowFind<'_>) -> RepoResult<Option<Follow>> {
+        Ok(None)
+    }
+
+    async fn accept_follow(&self, _follow_id: &str) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn remove_follow(&self, _follow_id: &str) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn fetch_follower_inboxes(&self, _user_id: &str) -> RepoResult<Vec<String>> {
+        Ok(vec![])
+    }
+
+    async fn fetch_local_follower_ids(&self, _user_id: &str) -> RepoResult<Vec<String>> {
+        Ok(vec![])
+    }
+
+    async fn fetch_follow_requests(&self, _user_id: &str) -> RepoResult<Vec<FollowRelated>> {
+        Ok(vec![])
+    }
+
+    async fn count_related(
+        &self,
+        _user_id: &str,
+        _direction: FollowDirection,
+    ) -> RepoResult<usize> {
+        Ok(0)
+    }
+
+    async fn fetch_related(
+        &self,
+        _user_id: &str,
+        _direction: FollowDirection,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<FollowRelated>> {
+        Ok(vec![])
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use monaxia_data::instance::{InstanceActor, InstanceActorRegistration};
+use monaxia_repository::{
+    repo::{instance::InstanceRepository, Repository},
+    RepoResult,
+};
+
+pub struct InstanceRepositoryImpl;
+
+impl Repository for InstanceRepositoryImpl {}
+
+#[async_trait]
+impl InstanceRepository for InstanceRepositoryImpl {
+    async fn find_instance_actor(&self) -> RepoResult<Option<InstanceActor>> {
+        Ok(None)
+    }
+
+    async fn register_instance_actor(
+        &self,
+        _registration: InstanceActorRegistration,
+    ) -> RepoResult<bool> {
+        Ok(true)
+    }
+
+    async fn fetch_private_key(&self) -> RepoResult<Option<String>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use monaxia_data::note::{Note, NoteRegistration, NoteTombstone};
+use monaxia_repository::{
+    repo::{note::NoteRepository, Repository},
+    RepoResult,
+};
+
+pub struct NoteRepositoryImpl;
+
+impl Repository for NoteRepositoryImpl {}
+
+#[async_trait]
+impl NoteRepository for NoteRepositoryImpl {
+    async fn register_note(&self, _registration: NoteRegistration) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn find_note(&self, _note_id: &str) -> RepoResult<Option<Note>> {
+        Ok(None)
+    }
+
+    async fn delete_note(&self, _note_id: &str, _note_uri: &str) -> RepoResult<bool> {
+        Ok(false)
+    }
+
+    async fn find_tombstone(&self, _note_id: &str) -> RepoResult<Option<NoteTombstone>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use monaxia_data::reaction::{Reaction, ReactionKind, ReactionRegistration};
+use monaxia_repository::{
+    repo::{
+        reaction::{ReactionFind, ReactionRepository},
+        Repository,
+    },
+    RepoResult,
+};
+
+pub struct ReactionRepositoryImpl;
+
+impl Repository for ReactionRepositoryImpl {}
+
+#[async_trait]
+impl ReactionRepository for ReactionRepositoryImpl {
+    async fn register_reaction(&self, _registration: ReactionRegistration) -> RepoResult<bool> {
+        Ok(true)
+    }
+
+    async fn find_reaction(
+        &self,
+        _kind: ReactionKind,
+        _reaction_find: ReactionFind<'_>,
+    ) -> RepoResult<Option<Reaction>> {
+        Ok(None)
+    }
+
+    async fn remove_reaction(&self, _kind: ReactionKind, _reaction_id: &str) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn remove_reactions_to(&self, _object_uri: &str) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn count_reactions(&self, _kind: ReactionKind, _object_uri: &str) -> RepoResult<usize> {
+        Ok(0)
+    }
+}
@@ -1,8 +1,11 @@
 use async_trait::async_trait;
-use monaxia_data::user::{LocalUser, LocalUserRegistration, RemoteUserRegistration};
+use monaxia_data::user::{
+    LocalUser, LocalUserKeyRotation, LocalUserProfile, LocalUserRegistration, RemoteUser,
+    RemoteUserRegistration,
+};
 use monaxia_repository::{
     repo::{
-        user::{UserFind, UserRepository},
+        user::{RemoteUserFind, UserFind, UserRepository},
         Repository,
     },
     RepoResult,
@@ -41,4 +44,39 @@ impl UserRepository for UserRepositoryImpl {
     async fn find_local_user(&self, _user_find: UserFind<'_>) -> RepoResult<Option<LocalUser>> {
         Ok(None)
     }
+
+    async fn fetch_local_users(&self) -> RepoResult<Vec<LocalUser>> {
+        Ok(vec![])
+    }
+
+    async fn update_local_profile(
+        &self,
+        _user_id: &str,
+        _profile: LocalUserProfile,
+    ) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn rotate_key(&self, _user_id: &str, _rotation: LocalUserKeyRotation) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn fetch_private_key(&self, _user_id: &str) -> RepoResult<Option<String>> {
+        Ok(None)
+    }
+
+    async fn remove_user(&self, _user_id: &str, _notes_url: &str) -> RepoResult<()> {
+        Ok(())
+    }
+
+    async fn fetch_remote_inboxes(&self) -> RepoResult<Vec<String>> {
+        Ok(vec![])
+    }
+
+    async fn find_remote_user(
+        &self,
+        _remote_user_find: RemoteUserFind<'_>,
+    ) -> RepoResult<Option<RemoteUser>> {
+        Ok(None)
+    }
 }
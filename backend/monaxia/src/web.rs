@@ -1,6 +1,8 @@
+mod collection;
 mod error;
 mod extract;
 mod jsonld;
+mod page;
 mod routes;
 pub mod state;
 
@@ -16,16 +18,23 @@ use tokio::{select, signal};
 use tower_http::trace::{OnRequest, TraceLayer};
 use tracing::{debug, info, Span};
 
-use crate::worker::{create_queues, spawn_workers};
+use crate::worker::{create_queues, spawn_workers, WorkerState};
 
 pub async fn run_server(config: Config) -> Result<()> {
-    // start workers
+    let bind_addr = config.server.bind;
     let (producer, consumers) = create_queues(&config).await?;
-    spawn_workers(consumers).await;
+    let state = state::construct_state(config, producer.clone()).await?;
+
+    // start workers
+    let worker_state = WorkerState {
+        config: state.config.clone(),
+        producer: state.producer.clone(),
+        container: state.container.clone(),
+        client: state.client.clone(),
+    };
+    spawn_workers(worker_state, consumers).await;
 
     // start web server
-    let bind_addr = config.server.bind;
-    let state = state::construct_state(config, producer.clone()).await?;
     let routes = construct_router(state);
 
     let server = Server::bind(&bind_addr)
@@ -51,17 +60,34 @@ fn construct_router(state_source: state::AppState) -> Router<()> {
             "/.well-known/nodeinfo",
             get(routes::meta::wellknown_nodeinfo),
         )
-        .route("/nodeinfo/2.1", get(routes::meta::nodeinfo));
+        .route("/nodeinfo/2.1", get(routes::meta::nodeinfo))
+        .route(
+            "/authorize_interaction",
+            get(routes::meta::authorize_interaction),
+        );
     let users_router = Router::new()
         .route("/:user_id", get(routes::users::actor))
         .route("/:user_id/inbox", post(routes::users::inbox))
-        .route("/:user_id/outbox", get(routes::users::outbox));
+        .route("/:user_id/outbox", get(routes::users::outbox))
+        .route("/:user_id/followers", get(routes::users::followers))
+        .route("/:user_id/following", get(routes::users::following));
 
     // layers
     let trace_layer = TraceLayer::new_for_http().on_request(OnRequestHandler);
 
     Router::new()
         .merge(meta_router)
+        .route("/inbox", post(routes::inbox::shared_inbox))
+        .route("/actor", get(routes::instance::actor))
+        .route(
+            "/activities/:activity_id",
+            get(routes::activities::activity),
+        )
+        .route("/notes/:note_id", get(routes::notes::note))
+        .route("/notes/:note_id/likes", get(routes::notes::likes))
+        .route("/notes/:note_id/shares", get(routes::notes::shares))
+        .route("/@:username", get(routes::profiles::profile))
+        .route("/@:username/:note_id", get(routes::profiles::profile_note))
         .nest("/users", users_router)
         .with_state(state_source)
         .layer(trace_layer)
@@ -0,0 +1,138 @@
+use crate::web::jsonld::{JsonLd, JSONLD_ACTIVITYSTREAMS};
+
+use monaxia_data::{id::ORDER58_ZERO, pagination::Pagination};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Number of items in a collection page.
+pub const COLLECTION_PAGE_SIZE: usize = 20;
+
+/// Query parameters for paginated collections.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectionQuery {
+    #[serde(default)]
+    pub page: bool,
+    pub max_id: Option<String>,
+    pub min_id: Option<String>,
+}
+
+impl CollectionQuery {
+    pub fn pagination(&self) -> Pagination {
+        Pagination {
+            max_id: self.max_id.clone(),
+            min_id: self.min_id.clone(),
+            limit: COLLECTION_PAGE_SIZE,
+        }
+    }
+}
+
+/// Response type of ActivityStreams OrderedCollection.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename = "OrderedCollection", rename_all = "camelCase")]
+pub struct ResponseOrderedCollection {
+    #[serde(flatten)]
+    pub jsonld: JsonLd,
+
+    pub id: Url,
+    pub total_items: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first: Option<Url>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last: Option<Url>,
+}
+
+/// Response type of ActivityStreams OrderedCollectionPage.
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename = "OrderedCollectionPage",
+    rename_all = "camelCase"
+)]
+pub struct ResponseOrderedCollectionPage<T> {
+    #[serde(flatten)]
+    pub jsonld: JsonLd,
+
+    pub id: Url,
+    pub part_of: Url,
+    pub ordered_items: Vec<T>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<Url>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<Url>,
+}
+
+/// Constructs OrderedCollection which links to the newest and oldest pages.
+pub fn ordered_collection(collection_url: &Url, total_items: usize) -> ResponseOrderedCollection {
+    ResponseOrderedCollection {
+        jsonld: JSONLD_ACTIVITYSTREAMS.clone(),
+        id: collection_url.clone(),
+        total_items,
+        first: Some(page_url(collection_url, None, None)),
+        last: Some(page_url(collection_url, None, Some(ORDER58_ZERO))),
+    }
+}
+
+/// Constructs OrderedCollection which exposes only the number of items.
+pub fn ordered_collection_total_only(
+    collection_url: &Url,
+    total_items: usize,
+) -> ResponseOrderedCollection {
+    ResponseOrderedCollection {
+        jsonld: JSONLD_ACTIVITYSTREAMS.clone(),
+        id: collection_url.clone(),
+        total_items,
+        first: None,
+        last: None,
+    }
+}
+
+/// Constructs OrderedCollectionPage from items paired with their order58 IDs.
+/// Items must be sorted in descending order.
+pub fn ordered_collection_page<T>(
+    collection_url: &Url,
+    query: &CollectionQuery,
+    items: Vec<(String, T)>,
+) -> ResponseOrderedCollectionPage<T> {
+    let id = page_url(
+        collection_url,
+        query.max_id.as_deref(),
+        query.min_id.as_deref(),
+    );
+    let next = match items.last() {
+        Some((last_id, _)) if items.len() >= COLLECTION_PAGE_SIZE => {
+            Some(page_url(collection_url, Some(last_id), None))
+        }
+        _ => None,
+    };
+    let prev = items
+        .first()
+        .map(|(first_id, _)| page_url(collection_url, None, Some(first_id)));
+
+    ResponseOrderedCollectionPage {
+        jsonld: JSONLD_ACTIVITYSTREAMS.clone(),
+        id,
+        part_of: collection_url.clone(),
+        ordered_items: items.into_iter().map(|(_, item)| item).collect(),
+        next,
+        prev,
+    }
+}
+
+fn page_url(collection_url: &Url, max_id: Option<&str>, min_id: Option<&str>) -> Url {
+    let mut url = collection_url.clone();
+    {
+        let mut pairs = url.query_pairs_mut();
+        pairs.append_pair("page", "true");
+        if let Some(max_id) = max_id {
+            pairs.append_pair("max_id", max_id);
+        }
+        if let Some(min_id) = min_id {
+            pairs.append_pair("min_id", min_id);
+        }
+    }
+    url
+}
@@ -22,6 +22,12 @@ pub enum ErrorType {
     /// Something not found.
     NotFound,
 
+    /// HTTP Signature was missing or invalid.
+    InvalidSignature,
+
+    /// Requester belongs to a suspended domain.
+    DomainSuspended,
+
     /// Other error.
     OtherError,
 }
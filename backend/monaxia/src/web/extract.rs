@@ -1,9 +1,15 @@
+mod activity;
 mod ap;
+mod note;
 mod reject;
+mod signature;
 mod user;
 
 pub use self::{
+    activity::PathLocalActivity,
     ap::{ApAccept, ApDualAccept, ApJson, MustAcceptActivityJson},
-    reject::{MonaxiaRejection, RjForm, RjJson, RjQuery},
-    user::PathLocalUser,
+    note::PathLocalNote,
+    reject::RjQuery,
+    signature::{SignedApJson, SignedFetch},
+    user::{PathLocalUser, PathVanityUser},
 };
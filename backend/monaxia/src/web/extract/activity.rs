@@ -0,0 +1,52 @@
+use super::reject::RjPath;
+use crate::web::{
+    error::{map_err_repository, ErrorResponse, ErrorType},
+    state::AppState,
+};
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Path},
+    http::{request::Parts, StatusCode},
+};
+use axum_extra::extract::WithRejection;
+use monaxia_data::activity::Activity;
+use serde::Deserialize;
+
+/// Activity of a local user specified by `activity_id` path parameter.
+#[derive(Debug, Clone)]
+pub struct PathLocalActivity(pub Activity);
+
+#[async_trait]
+impl FromRequestParts<AppState> for PathLocalActivity {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let WithRejection(Path(PathActivityId { activity_id }), _) =
+            RjPath::<PathActivityId>::from_request_parts(parts, state)
+                .await
+                .map_err(|e| e.into_mx_error(ErrorType::InvalidRequest))?;
+        let activity = state
+            .container
+            .activity
+            .find_activity(&activity_id)
+            .await
+            .map_err(map_err_repository)?;
+        match activity {
+            Some(activity) => Ok(PathLocalActivity(activity)),
+            None => Err(ErrorResponse {
+                status_code: StatusCode::NOT_FOUND,
+                error: ErrorType::NotFound,
+                reason: format!("activity {activity_id} not found"),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PathActivityId {
+    pub activity_id: String,
+}
@@ -123,7 +123,7 @@ where
     }
 }
 
-fn ap_accept(headers: &HeaderMap) -> ApAccept {
+pub(super) fn ap_accept(headers: &HeaderMap) -> ApAccept {
     let Some(accept) = headers.get(ACCEPT) else {
         return ApAccept::Html;
     };
@@ -149,7 +149,7 @@ fn ap_accept(headers: &HeaderMap) -> ApAccept {
     ApAccept::Html
 }
 
-fn ap_json_content_type(headers: &HeaderMap) -> bool {
+pub(super) fn ap_json_content_type(headers: &HeaderMap) -> bool {
     let Some(content_type) = headers.get(CONTENT_TYPE) else {
         return false;
     };
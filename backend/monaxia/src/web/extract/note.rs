@@ -0,0 +1,86 @@
+use super::{
+    ap::{ap_accept, ApAccept, ApJson},
+    reject::RjPath,
+};
+use crate::{
+    constant::ap::ACTIVITYSTREAMS_CONTEXT,
+    federation::note::tombstone_object,
+    web::{
+        error::{map_err_repository, ErrorResponse, ErrorType},
+        page::{tombstone_page, vary_accept},
+        state::AppState,
+    },
+};
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Path},
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::WithRejection;
+use monaxia_data::note::Note;
+use serde::Deserialize;
+
+/// Local note specified by `note_id` path parameter.
+/// Deleted notes are rejected with `410 Gone` and Tombstone object, or HTML page
+/// for browsers.
+#[derive(Debug, Clone)]
+pub struct PathLocalNote(pub Note);
+
+#[async_trait]
+impl FromRequestParts<AppState> for PathLocalNote {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let WithRejection(Path(PathNoteId { note_id }), _) =
+            RjPath::<PathNoteId>::from_request_parts(parts, state)
+                .await
+                .map_err(|e| e.into_mx_error(ErrorType::InvalidRequest).into_response())?;
+        let note = state
+            .container
+            .note
+            .find_note(&note_id)
+            .await
+            .map_err(|e| map_err_repository(e).into_response())?;
+        if let Some(note) = note {
+            return Ok(PathLocalNote(note));
+        }
+
+        let tombstone = state
+            .container
+            .note
+            .find_tombstone(&note_id)
+            .await
+            .map_err(|e| map_err_repository(e).into_response())?;
+        match tombstone {
+            Some(tombstone) => {
+                let response = match ap_accept(&parts.headers) {
+                    ApAccept::ActivityJson => {
+                        let mut object = tombstone_object(&state.config, &tombstone);
+                        object["@context"] = ACTIVITYSTREAMS_CONTEXT.into();
+                        ApJson(object).into_response()
+                    }
+                    ApAccept::Html => {
+                        tombstone_page(&state.config, &tombstone).into_response_with(&state.config)
+                    }
+                };
+                Err((StatusCode::GONE, vary_accept(response)).into_response())
+            }
+            None => Err(ErrorResponse {
+                status_code: StatusCode::NOT_FOUND,
+                error: ErrorType::NotFound,
+                reason: format!("note {note_id} not found"),
+            }
+            .into_response()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PathNoteId {
+    pub note_id: String,
+}
@@ -0,0 +1,240 @@
+use super::ap::ap_json_content_type;
+use crate::{
+    federation::{
+        resolver::{refresh_key_owner, resolve_key_owner},
+        signature::{
+            parse_public_key_pem, signing_string, verify_date, verify_digest, verify_signature,
+            SignatureError, SignatureHeader, REQUIRED_HEADERS,
+        },
+        FederationError,
+    },
+    web::{
+        error::{map_err_repository, ErrorResponse, ErrorType},
+        state::AppState,
+    },
+};
+
+use async_trait::async_trait;
+use axum::{
+    body::{Bytes, HttpBody},
+    extract::{FromRequest, FromRequestParts, OriginalUri},
+    http::{request::Parts, HeaderMap, Method, Request, StatusCode, Uri},
+    BoxError,
+};
+use monaxia_data::user::RemoteUser;
+use serde::de::DeserializeOwned;
+use tracing::warn;
+
+const SIGNATURE_HEADER: &str = "signature";
+const DIGEST_HEADER: &str = "digest";
+
+/// Verifies HTTP Signature of the request and extracts ActivityPub JSON body.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct SignedApJson<T> {
+    /// Remote user who owns the signing key.
+    pub signer: RemoteUser,
+
+    /// Deserialized body.
+    pub body: T,
+}
+
+#[async_trait]
+impl<T, B> FromRequest<AppState, B> for SignedApJson<T>
+where
+    T: DeserializeOwned,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request(req: Request<B>, state: &AppState) -> Result<Self, Self::Rejection> {
+        if !ap_json_content_type(req.headers()) {
+            return Err(ErrorResponse {
+                error: ErrorType::MissingContentType,
+                reason: "Content-Type must be application/activity+json".into(),
+                status_code: StatusCode::UNPROCESSABLE_ENTITY,
+            });
+        }
+
+        let method = req.method().clone();
+        let headers = req.headers().clone();
+        let path = request_path(req.uri(), req.extensions().get());
+
+        let signature = parse_signature(
+            &headers,
+            REQUIRED_HEADERS.into_iter().chain([DIGEST_HEADER]),
+        )?;
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|r| ErrorResponse {
+                status_code: r.status(),
+                error: ErrorType::InvalidRequest,
+                reason: r.body_text(),
+            })?;
+        header_str(&headers, DIGEST_HEADER)
+            .ok_or_else(|| SignatureError::MissingHeader(DIGEST_HEADER.into()))
+            .and_then(|d| verify_digest(d, &body))
+            .map_err(map_err_signature)?;
+
+        let signer = verify_signer(state, &signature, &method, &path, &headers).await?;
+
+        let body = serde_json::from_slice(&body).map_err(|e| ErrorResponse {
+            status_code: StatusCode::BAD_REQUEST,
+            error: ErrorType::InvalidRequest,
+            reason: e.to_string(),
+        })?;
+
+        Ok(SignedApJson { signer, body })
+    }
+}
+
+/// Verifies HTTP Signature of GET request if present.
+/// Unsigned requests are rejected only if authorized fetch is enabled.
+#[derive(Debug, Clone)]
+pub enum SignedFetch {
+    /// Not signed while authorized fetch is disabled.
+    Unrestricted,
+
+    /// Signed by the remote user.
+    Signed(Box<RemoteUser>),
+
+    /// Not signed while authorized fetch is enabled.
+    Unsigned,
+}
+
+impl SignedFetch {
+    /// Whether the requester may read the content.
+    pub fn is_authorized(&self) -> bool {
+        !matches!(self, SignedFetch::Unsigned)
+    }
+
+    /// Rejects unsigned requests with 401.
+    pub fn ensure_authorized(&self) -> Result<(), ErrorResponse> {
+        if self.is_authorized() {
+            return Ok(());
+        }
+        Err(ErrorResponse {
+            status_code: StatusCode::UNAUTHORIZED,
+            error: ErrorType::InvalidSignature,
+            reason: "request must be signed".into(),
+        })
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for SignedFetch {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if !parts.headers.contains_key(SIGNATURE_HEADER) {
+            return Ok(if state.config.server.authorized_fetch {
+                SignedFetch::Unsigned
+            } else {
+                SignedFetch::Unrestricted
+            });
+        }
+
+        let path = request_path(&parts.uri, parts.extensions.get());
+        let signature = parse_signature(&parts.headers, REQUIRED_HEADERS)?;
+        let signer = verify_signer(state, &signature, &parts.method, &path, &parts.headers).await?;
+        Ok(SignedFetch::Signed(Box::new(signer)))
+    }
+}
+
+/// Path and query of the request, which is signed as `(request-target)`.
+fn request_path(uri: &Uri, original_uri: Option<&OriginalUri>) -> String {
+    match original_uri {
+        Some(OriginalUri(uri)) => uri.path_and_query().map(|pq| pq.to_string()),
+        None => uri.path_and_query().map(|pq| pq.to_string()),
+    }
+    .unwrap_or_else(|| "/".into())
+}
+
+/// Parses `Signature` header and checks that required headers are signed and fresh.
+fn parse_signature<'a>(
+    headers: &HeaderMap,
+    required: impl IntoIterator<Item = &'a str>,
+) -> Result<SignatureHeader, ErrorResponse> {
+    let signature = header_str(headers, SIGNATURE_HEADER)
+        .ok_or_else(|| SignatureError::MissingHeader(SIGNATURE_HEADER.into()))
+        .and_then(SignatureHeader::parse)
+        .map_err(map_err_signature)?;
+    signature
+        .ensure_signed(required)
+        .map_err(map_err_signature)?;
+    header_str(headers, "date")
+        .ok_or_else(|| SignatureError::MissingHeader("date".into()))
+        .and_then(verify_date)
+        .map_err(map_err_signature)?;
+    Ok(signature)
+}
+
+/// Resolves the owner of the signing key and verifies the signature with it.
+/// The owner is refetched once if the known key does not match, since keys may be rotated with the same ID.
+async fn verify_signer(
+    state: &AppState,
+    signature: &SignatureHeader,
+    method: &Method,
+    path: &str,
+    headers: &HeaderMap,
+) -> Result<RemoteUser, ErrorResponse> {
+    let signer = resolve_key_owner(&state.container, &state.client, &signature.key_id)
+        .await
+        .map_err(map_err_key_resolution)?;
+    let signing =
+        signing_string(&signature.headers, method, path, headers).map_err(map_err_signature)?;
+    let verify = |signer: &RemoteUser| {
+        parse_public_key_pem(&signer.public_key)
+            .and_then(|key| verify_signature(key, &signing, &signature.signature))
+    };
+
+    let err = match verify(&signer) {
+        Ok(()) => return Ok(signer),
+        Err(e) => e,
+    };
+    let refreshed = refresh_key_owner(&state.container, &state.client, &signer)
+        .await
+        .map_err(map_err_key_resolution)?;
+    let Some(signer) = refreshed else {
+        return Err(map_err_signature(err));
+    };
+    verify(&signer).map_err(map_err_signature)?;
+    Ok(signer)
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn map_err_signature(err: SignatureError) -> ErrorResponse {
+    ErrorResponse {
+        status_code: StatusCode::UNAUTHORIZED,
+        error: ErrorType::InvalidSignature,
+        reason: err.to_string(),
+    }
+}
+
+fn map_err_key_resolution(err: FederationError) -> ErrorResponse {
+    match err {
+        FederationError::Repository(e) => map_err_repository(e),
+        FederationError::Suspended(domain) => ErrorResponse {
+            status_code: StatusCode::FORBIDDEN,
+            error: ErrorType::DomainSuspended,
+            reason: format!("domain {domain} is suspended"),
+        },
+        otherwise => {
+            warn!("failed to resolve signing key: {otherwise}");
+            ErrorResponse {
+                status_code: StatusCode::UNAUTHORIZED,
+                error: ErrorType::InvalidSignature,
+                reason: "signing key could not be resolved".into(),
+            }
+        }
+    }
+}
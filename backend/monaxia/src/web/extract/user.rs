@@ -53,3 +53,42 @@ impl FromRequestParts<AppState> for PathLocalUser {
 struct PathUserId {
     pub user_id: String,
 }
+
+/// Local user specified by `:username` path parameter, used for vanity URLs.
+#[derive(Debug, Clone)]
+pub struct PathVanityUser(pub LocalUser);
+
+#[async_trait]
+impl FromRequestParts<AppState> for PathVanityUser {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let WithRejection(Path(PathUsername { username }), _) =
+            RjPath::<PathUsername>::from_request_parts(parts, state)
+                .await
+                .map_err(|e| e.into_mx_error(ErrorType::InvalidRequest))?;
+        let local_user = state
+            .container
+            .user
+            .find_local_user(UserFind::Username(&username))
+            .await
+            .map_err(map_err_repository)?;
+        let Some(local_user) = local_user else {
+            return Err(ErrorResponse {
+                status_code: StatusCode::NOT_FOUND,
+                error: ErrorType::NotFound,
+                reason: format!("local user {username} not found"),
+            });
+        };
+
+        Ok(PathVanityUser(local_user))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PathUsername {
+    pub username: String,
+}
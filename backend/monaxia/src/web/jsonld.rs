@@ -1,30 +1,32 @@
+use crate::federation::actor::actor_context;
+
 use std::fmt::{Formatter, Result as FmtResult};
 
+use monaxia_data::ap::vocabulary::OneOrMany;
 use once_cell::sync::Lazy;
 use serde::{
     de::{value::MapAccessDeserializer, Error as SerdeDeError, MapAccess, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use serde_json::{json, Value as JsonValue};
+use serde_json::Value as JsonValue;
 use url::Url;
 
+/// `@context` of actor documents, same as `actor_context`.
 pub static JSONLD_OBJECT: Lazy<JsonLd> = Lazy::new(|| JsonLd {
-    context: vec![
-        JsonLdContext::Url(
-            Url::parse("https://www.w3.org/ns/activitystreams").expect("invalid context"),
-        ),
-        JsonLdContext::Url(Url::parse("https://w3id.org/security/v1").expect("invalid context")),
-        JsonLdContext::Object(json!({
-            "toot": "http://joinmastodon.org/ns#",
-            "discoverable": "toot:discoverable",
-        })),
-    ],
+    context: serde_json::from_value(actor_context()).expect("invalid context"),
+});
+
+pub static JSONLD_ACTIVITYSTREAMS: Lazy<JsonLd> = Lazy::new(|| JsonLd {
+    context: vec![JsonLdContext::Url(
+        Url::parse("https://www.w3.org/ns/activitystreams").expect("invalid context"),
+    )],
 });
 
 /// Contains `@context` property. supposed to used with `#[serde(flatten)]`.
+/// Single string or object is also accepted as `@context` when deserializing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonLd {
-    #[serde(rename = "@context")]
+    #[serde(rename = "@context", deserialize_with = "deserialize_contexts")]
     pub context: Vec<JsonLdContext>,
 }
 
@@ -79,3 +81,11 @@ impl<'de> Deserialize<'de> for JsonLdContext {
         deserializer.deserialize_any(ContextVisitor)
     }
 }
+
+fn deserialize_contexts<'de, D>(deserializer: D) -> Result<Vec<JsonLdContext>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let contexts: OneOrMany<JsonLdContext> = Deserialize::deserialize(deserializer)?;
+    Ok(contexts.into_vec())
+}
@@ -0,0 +1,276 @@
+use crate::{
+    constant::mime::APPLICATION_ACTIVITY_JSON,
+    federation::{
+        local::{local_note_page_url, local_note_url, local_profile_url, local_user_url},
+        note::escape_html,
+    },
+};
+
+use axum::{
+    http::{header::VARY, HeaderValue},
+    response::{Html, IntoResponse, Response},
+};
+use monaxia_data::{
+    config::Config,
+    note::{Note, NoteTombstone},
+    user::LocalUser,
+};
+use url::Url;
+
+/// Server-side rendered page for browsers, with OpenGraph metadata.
+#[derive(Debug, Clone)]
+pub struct HtmlPage {
+    /// `og:type` value.
+    pub kind: &'static str,
+    pub title: String,
+    pub description: String,
+    pub url: Url,
+    pub image: Option<String>,
+
+    /// URL of ActivityPub representation of the same resource, if any.
+    pub alternate: Option<Url>,
+
+    /// Inner HTML of `<main>`.
+    pub body: String,
+}
+
+impl HtmlPage {
+    fn render(&self, site_name: &str) -> String {
+        let mut meta = vec![
+            ("og:type", self.kind.to_string()),
+            ("og:site_name", site_name.to_string()),
+            ("og:title", self.title.clone()),
+            ("og:description", self.description.clone()),
+            ("og:url", self.url.to_string()),
+        ];
+        if let Some(image) = &self.image {
+            meta.push(("og:image", image.clone()));
+        }
+        let meta_tags: String = meta
+            .iter()
+            .map(|(property, content)| {
+                format!(
+                    r#"<meta property="{property}" content="{}">"#,
+                    escape_html(content)
+                )
+            })
+            .collect();
+        let alternate_link = match &self.alternate {
+            Some(alternate) => format!(
+                r#"<link rel="alternate" type="{APPLICATION_ACTIVITY_JSON}" href="{}">"#,
+                escape_html(alternate.as_str())
+            ),
+            None => String::new(),
+        };
+
+        format!(
+            concat!(
+                "<!DOCTYPE html>",
+                r#"<html><head><meta charset="utf-8">"#,
+                "<title>{title}</title>",
+                r#"<meta name="description" content="{description}">"#,
+                "{meta_tags}",
+                r#"<link rel="canonical" href="{url}">"#,
+                "{alternate_link}",
+                "</head><body><main>{body}</main></body></html>",
+            ),
+            title = escape_html(&self.title),
+            description = escape_html(&self.description),
+            meta_tags = meta_tags,
+            url = escape_html(self.url.as_str()),
+            alternate_link = alternate_link,
+            body = self.body,
+        )
+    }
+
+    pub fn into_response_with(self, config: &Config) -> Response {
+        Html(self.render(&config.cached.acct_origin())).into_response()
+    }
+}
+
+/// Marks the response as negotiated by `Accept` header, for resources served
+/// both as ActivityPub object and HTML page.
+pub fn vary_accept(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert(VARY, HeaderValue::from_static("Accept"));
+    response
+}
+
+/// Constructs the profile page of the local user.
+pub fn profile_page(config: &Config, local_user: &LocalUser) -> HtmlPage {
+    let profile = &local_user.profile;
+    let handle = format!("@{}@{}", local_user.username, config.cached.acct_origin());
+    let title = match &profile.display_name {
+        Some(display_name) => format!("{display_name} ({handle})"),
+        None => handle.clone(),
+    };
+
+    let mut body = String::new();
+    if let Some(header_url) = &profile.header_url {
+        body.push_str(&format!(
+            r#"<img class="header" src="{}" alt="">"#,
+            escape_html(header_url)
+        ));
+    }
+    if let Some(avatar_url) = &profile.avatar_url {
+        body.push_str(&format!(
+            r#"<img class="avatar" src="{}" alt="">"#,
+            escape_html(avatar_url)
+        ));
+    }
+    body.push_str(&format!(
+        "<h1>{}</h1><p>{}</p>",
+        escape_html(
+            profile
+                .display_name
+                .as_deref()
+                .unwrap_or(&local_user.username)
+        ),
+        escape_html(&handle),
+    ));
+    if let Some(summary) = &profile.summary {
+        body.push_str(&format!(r#"<div class="summary">{summary}</div>"#));
+    }
+    if !profile.fields.is_empty() {
+        let rows: String = profile
+            .fields
+            .iter()
+            .map(|f| format!("<dt>{}</dt><dd>{}</dd>", escape_html(&f.name), f.value))
+            .collect();
+        body.push_str(&format!("<dl>{rows}</dl>"));
+    }
+
+    HtmlPage {
+        kind: "profile",
+        title,
+        description: profile
+            .summary
+            .as_deref()
+            .map(strip_tags)
+            .unwrap_or_default(),
+        url: local_profile_url(config, &local_user.username),
+        image: profile.avatar_url.clone(),
+        alternate: Some(local_user_url(config, &local_user.id)),
+        body,
+    }
+}
+
+/// Constructs the page of the note posted by the local user.
+pub fn note_page(config: &Config, author: &LocalUser, note: &Note) -> HtmlPage {
+    let handle = format!("@{}@{}", author.username, config.cached.acct_origin());
+    let author_name = author
+        .profile
+        .display_name
+        .as_deref()
+        .unwrap_or(&author.username);
+    let note_url = local_note_url(config, &note.id);
+    let page_url = local_note_page_url(config, &author.username, &note.id);
+
+    let body = format!(
+        concat!(
+            r#"<article><header><a href="{profile_url}">{author_name}</a> {handle}</header>"#,
+            r#"<div class="content">{content}</div>"#,
+            r#"<footer><a href="{page_url}"><time datetime="{published}">{published}</time></a></footer>"#,
+            "</article>",
+        ),
+        profile_url = escape_html(local_profile_url(config, &author.username).as_str()),
+        author_name = escape_html(author_name),
+        handle = escape_html(&handle),
+        content = note.content,
+        page_url = escape_html(page_url.as_str()),
+        published = note.published.date(),
+    );
+
+    HtmlPage {
+        kind: "article",
+        title: format!("{author_name} ({handle})"),
+        description: strip_tags(&note.content),
+        url: page_url,
+        image: author.profile.avatar_url.clone(),
+        alternate: Some(note_url),
+        body,
+    }
+}
+
+/// Constructs the page shown in place of the deleted note.
+pub fn tombstone_page(config: &Config, tombstone: &NoteTombstone) -> HtmlPage {
+    let note_url = local_note_url(config, &tombstone.id);
+    HtmlPage {
+        kind: "article",
+        title: "Deleted note".into(),
+        description: "This note has been deleted.".into(),
+        url: note_url.clone(),
+        image: None,
+        alternate: Some(note_url),
+        body: "<article><p>This note has been deleted.</p></article>".into(),
+    }
+}
+
+/// Constructs the page to interact with the remote object, linked from WebFinger.
+/// Local users have no web session, so it shows the command to follow instead.
+pub fn interaction_page(config: &Config, uri: &str) -> HtmlPage {
+    let mut url = config
+        .cached
+        .server_base_url()
+        .join("/authorize_interaction")
+        .expect("URL error");
+    url.query_pairs_mut().append_pair("uri", uri);
+
+    let body = format!(
+        concat!(
+            "<h1>Follow {uri}</h1>",
+            "<p>Run the following command on this server to follow it.</p>",
+            "<pre><code>monaxia user follow &lt;username&gt; {uri}</code></pre>",
+        ),
+        uri = escape_html(uri),
+    );
+
+    HtmlPage {
+        kind: "website",
+        title: format!("Follow {uri}"),
+        description: format!("Follow {uri} from {}", config.cached.acct_origin()),
+        url,
+        image: None,
+        alternate: None,
+        body,
+    }
+}
+
+/// Extracts text from HTML fragment, for metadata.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_tags;
+
+    #[test]
+    fn strip_tags_extracts_text() {
+        assert_eq!(
+            strip_tags("<p>Hello,<br>world &amp; &lt;you&gt;</p><p>bye</p>"),
+            "Hello, world & <you> bye"
+        );
+    }
+}
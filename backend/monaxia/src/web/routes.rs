@@ -1,8 +1,29 @@
+pub mod activities {
+    mod endpoint;
+    pub use endpoint::*;
+}
+pub mod inbox {
+    mod endpoint;
+    pub use endpoint::*;
+}
+pub mod instance {
+    mod endpoint;
+    mod schema;
+    pub use endpoint::*;
+}
 pub mod meta {
     mod endpoint;
     mod schema;
     pub use endpoint::*;
 }
+pub mod notes {
+    mod endpoint;
+    pub use endpoint::*;
+}
+pub mod profiles {
+    mod endpoint;
+    pub use endpoint::*;
+}
 pub mod users {
     mod endpoint;
     mod schema;
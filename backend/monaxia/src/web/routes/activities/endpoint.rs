@@ -0,0 +1,64 @@
+use crate::{
+    federation::{json::object_id, note::is_addressed},
+    web::{
+        error::{map_err_generic, ErrorResponse, ErrorType, MxResult},
+        extract::{ApJson, MustAcceptActivityJson, PathLocalActivity, SignedFetch},
+        state::AppState,
+    },
+};
+
+use axum::{extract::State, http::StatusCode};
+use monaxia_data::activity::Activity;
+use serde_json::Value as JsonValue;
+
+pub async fn activity(
+    State(state): State<AppState>,
+    _: MustAcceptActivityJson,
+    fetch: SignedFetch,
+    PathLocalActivity(activity): PathLocalActivity,
+) -> MxResult<ApJson<JsonValue>> {
+    ensure_readable(&state, &activity, &fetch).await?;
+    fetch.ensure_authorized()?;
+
+    Ok(ApJson(activity.body))
+}
+
+/// Non-public activities are exposed only to signed fetches of their audience.
+/// Others get 404 as if the activity does not exist.
+async fn ensure_readable(
+    state: &AppState,
+    activity: &Activity,
+    fetch: &SignedFetch,
+) -> MxResult<()> {
+    let readable = match fetch {
+        _ if activity.public => true,
+        SignedFetch::Signed(signer) => {
+            let addressees = ["to", "cc"]
+                .into_iter()
+                .filter_map(|key| activity.body.get(key))
+                .flat_map(|value| match value {
+                    JsonValue::Array(values) => values.iter().collect(),
+                    value => vec![value],
+                })
+                .filter_map(object_id);
+            is_addressed(
+                &state.config,
+                &state.container,
+                &activity.user_id,
+                addressees,
+                signer,
+            )
+            .await
+            .map_err(|e| map_err_generic(e, StatusCode::INTERNAL_SERVER_ERROR))?
+        }
+        _ => false,
+    };
+    if !readable {
+        return Err(ErrorResponse {
+            status_code: StatusCode::NOT_FOUND,
+            error: ErrorType::NotFound,
+            reason: format!("activity {} not found", activity.id),
+        });
+    }
+    Ok(())
+}
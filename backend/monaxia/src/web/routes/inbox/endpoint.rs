@@ -0,0 +1,88 @@
+use crate::{
+    federation::json::{object_id, object_type},
+    web::{
+        error::{bail_other, map_err_generic, ErrorResponse, ErrorType, MxResult},
+        extract::SignedApJson,
+        state::AppState,
+    },
+};
+
+use std::time::Duration;
+
+use axum::{extract::State, http::StatusCode};
+use monaxia_data::{ap::context::canonicalize, user::RemoteUser};
+use monaxia_job::job::{Job, MxJob};
+use monaxia_queue::retry::Backoff;
+use serde_json::Value as JsonValue;
+use tracing::debug;
+
+/// Maximum retry count of processing an activity.
+const INBOX_MAX_RETRY: usize = 4;
+
+/// Backoff strategy of processing activities.
+const INBOX_BACKOFF: Backoff = Backoff::Exponential {
+    initial: Duration::from_secs(30),
+    factor: 2.0,
+};
+
+/// Server-wide inbox. Recipients are determined from addressing of the activity.
+pub async fn shared_inbox(
+    State(state): State<AppState>,
+    SignedApJson { signer, body }: SignedApJson<JsonValue>,
+) -> MxResult<StatusCode> {
+    enqueue_activity(&state, None, signer, body).await
+}
+
+/// Canonicalizes and validates the activity, then enqueues it for workers.
+pub async fn enqueue_activity(
+    state: &AppState,
+    recipient: Option<String>,
+    signer: RemoteUser,
+    activity: JsonValue,
+) -> MxResult<StatusCode> {
+    let activity = canonicalize(activity);
+    let activity_type = validate_activity(&signer, &activity)?;
+    debug!(
+        "received {activity_type} activity for {} signed by {}",
+        recipient.as_deref().unwrap_or("shared inbox"),
+        signer.uri
+    );
+
+    let job = Job::ProcessInbox {
+        recipient,
+        signer: signer.uri,
+        activity: activity.to_string(),
+    };
+    state
+        .producer
+        .enqueue(
+            MxJob::new_retrying(job, INBOX_MAX_RETRY, INBOX_BACKOFF),
+            None,
+        )
+        .await
+        .map_err(|e| map_err_generic(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Checks that the activity is well-formed and performed by the signer.
+/// Returns the type of the activity.
+fn validate_activity<'a>(signer: &RemoteUser, activity: &'a JsonValue) -> MxResult<&'a str> {
+    let Some(activity_type) = object_type(activity) else {
+        return bail_other(StatusCode::BAD_REQUEST, "activity must have type");
+    };
+    if activity.get("id").and_then(|id| id.as_str()).is_none() {
+        return bail_other(StatusCode::BAD_REQUEST, "activity must have id");
+    }
+
+    let actor = activity.get("actor").and_then(object_id);
+    if actor != Some(signer.uri.as_str()) {
+        return Err(ErrorResponse {
+            status_code: StatusCode::UNAUTHORIZED,
+            error: ErrorType::InvalidSignature,
+            reason: "actor does not match the signer".into(),
+        });
+    }
+
+    Ok(activity_type)
+}
@@ -0,0 +1,33 @@
+use super::schema::ResponseApplication;
+
+use crate::{
+    federation::instance::instance_actor,
+    web::{
+        error::{bail_other, map_err_repository, MxResult},
+        extract::{ApJson, MustAcceptActivityJson},
+        jsonld::JSONLD_OBJECT,
+        state::AppState,
+    },
+};
+
+use axum::{extract::State, http::StatusCode};
+
+pub async fn actor(
+    State(state): State<AppState>,
+    _: MustAcceptActivityJson,
+) -> MxResult<ApJson<ResponseApplication>> {
+    let Some(actor) = state
+        .container
+        .instance
+        .find_instance_actor()
+        .await
+        .map_err(map_err_repository)?
+    else {
+        return bail_other(StatusCode::NOT_FOUND, "instance actor is not generated");
+    };
+
+    Ok(ApJson(ResponseApplication {
+        jsonld: JSONLD_OBJECT.clone(),
+        actor: instance_actor(&state.config, &actor),
+    }))
+}
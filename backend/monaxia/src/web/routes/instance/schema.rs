@@ -0,0 +1,14 @@
+use monaxia_data::ap::vocabulary::Actor;
+use serde::Serialize;
+
+use crate::web::jsonld::JsonLd;
+
+/// Response type of ActivityPub Application object of the instance actor.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseApplication {
+    #[serde(flatten)]
+    pub jsonld: JsonLd,
+
+    #[serde(flatten)]
+    pub actor: Actor,
+}
@@ -1,13 +1,21 @@
 use super::schema::{
-    Nodeinfo, NodeinfoMetadata, NodeinfoServices, NodeinfoSoftware, NodeinfoUsage,
-    NodeinfoUsageUsers, WebfingerQuery, WellknownNodeinfo, WellknownNodeinfoLink,
+    AuthorizeInteractionQuery, Nodeinfo, NodeinfoMetadata, NodeinfoServices, NodeinfoSoftware,
+    NodeinfoUsage, NodeinfoUsageUsers, WebfingerQuery, WellknownNodeinfo, WellknownNodeinfoLink,
     WellknownWebfinger, WellknownWebfingerLink,
 };
 use crate::{
-    constant::{SOFTWARE_NAME, VERSION},
+    constant::{mime::APPLICATION_ACTIVITY_JSON, SOFTWARE_NAME, VERSION},
+    federation::{
+        instance::instance_username,
+        local::{
+            local_instance_actor_url, local_profile_url, local_user_id_of, local_user_url,
+            local_username_of,
+        },
+    },
     web::{
         error::{bail_other, map_err_generic, map_err_repository, MxResult},
         extract::RjQuery,
+        page::interaction_page,
         state::AppState,
     },
 };
@@ -15,12 +23,17 @@ use crate::{
 use axum::{
     extract::{Query, State},
     http::{header::CONTENT_TYPE, StatusCode},
-    response::Response,
+    response::{IntoResponse, Redirect, Response},
     Json,
 };
 use axum_extra::extract::WithRejection;
-use monaxia_data::{ap::Acct, config::UserRegistration};
-use monaxia_repository::repo::user::UserFind;
+use monaxia_data::{
+    ap::Acct,
+    config::{Config, UserRegistration},
+    user::LocalUser,
+};
+use monaxia_repository::{repo::user::UserFind, Container};
+use url::Url;
 
 pub async fn host_meta(State(state): State<AppState>) -> MxResult<Response<String>> {
     let server_base_url = state.config.cached.server_base_url();
@@ -42,38 +55,144 @@ pub async fn wellknown_webfinger(
     WithRejection(Query(query), _): RjQuery<WebfingerQuery>,
 ) -> MxResult<Response<String>> {
     let (config, container) = (state.config, state.container);
-
-    let acct = Acct::parse(&query.resource)
-        .map_err(|e| map_err_generic(e, StatusCode::UNPROCESSABLE_ENTITY))?;
-    if acct.origin() != config.cached.acct_origin() {
-        bail_other(StatusCode::NOT_FOUND, "origin does not match")?;
+    if is_instance_resource(&config, &query.resource) {
+        return Ok(jrd_response(&instance_webfinger(&config)));
     }
 
-    let Some(local_user) = container.user
-        .find_local_user(UserFind::Username(acct.username()))
-        .await
-        .map_err(map_err_repository)? else {
-        return bail_other(StatusCode::NOT_FOUND, format!("user {} not found", acct.username()));
+    let Some(local_user) = find_resource_user(&config, &container, &query.resource).await? else {
+        return bail_other(
+            StatusCode::NOT_FOUND,
+            format!("user {} not found", query.resource),
+        );
     };
 
-    let user_url = config
-        .cached
-        .server_base_url()
-        .join(&format!("/users/{}", local_user.id))
-        .expect("URL error");
+    let acct = Acct::new(&local_user.username, &config.cached.acct_origin())
+        .map_err(|e| map_err_generic(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+    let user_url = local_user_url(&config, &local_user.id);
+    let profile_url = local_profile_url(&config, &local_user.username);
+    let subscribe_template = format!(
+        "{}authorize_interaction?uri={{uri}}",
+        config.cached.server_base_url()
+    );
+
+    let aliases = vec![user_url.clone(), profile_url.clone()];
     let data = WellknownWebfinger {
         subject: acct.to_subject(),
+        aliases,
+        links: vec![
+            WellknownWebfingerLink {
+                rel: "self".into(),
+                r#type: Some(APPLICATION_ACTIVITY_JSON.into()),
+                href: Some(user_url),
+                template: None,
+            },
+            WellknownWebfingerLink {
+                rel: "http://webfinger.net/rel/profile-page".into(),
+                r#type: Some("text/html".into()),
+                href: Some(profile_url),
+                template: None,
+            },
+            WellknownWebfingerLink {
+                rel: "http://ostatus.org/schema/1.0/subscribe".into(),
+                r#type: None,
+                href: None,
+                template: Some(subscribe_template),
+            },
+        ],
+    };
+    Ok(jrd_response(&data))
+}
+
+/// Target of OStatus subscribe template, to interact with `uri` from this server.
+/// Local users are redirected to their profile pages. Since local users have no web session,
+/// others get a page showing how to follow them from the command line.
+pub async fn authorize_interaction(
+    State(state): State<AppState>,
+    WithRejection(Query(query), _): RjQuery<AuthorizeInteractionQuery>,
+) -> MxResult<Response> {
+    let (config, container) = (state.config, state.container);
+    if let Some(local_user) = find_resource_user(&config, &container, &query.uri).await? {
+        let profile_url = local_profile_url(&config, &local_user.username);
+        return Ok(Redirect::to(profile_url.as_str()).into_response());
+    }
+
+    Ok(interaction_page(&config, &query.uri).into_response_with(&config))
+}
+
+/// Finds the local user pointed by the resource, which is an `acct:` URI,
+/// an actor URL, or a profile URL.
+/// Fails with 422 if the resource is neither URL nor acct.
+async fn find_resource_user(
+    config: &Config,
+    container: &Container,
+    resource: &str,
+) -> MxResult<Option<LocalUser>> {
+    let url = Url::parse(resource)
+        .ok()
+        .filter(|url| url.scheme() == "http" || url.scheme() == "https");
+    let acct;
+    let find = match &url {
+        Some(url) => {
+            if let Some(user_id) = local_user_id_of(config, url.as_str()) {
+                UserFind::UserId(user_id)
+            } else if let Some(username) = local_username_of(config, url.as_str()) {
+                UserFind::Username(username)
+            } else {
+                return Ok(None);
+            }
+        }
+        None => {
+            acct = Acct::parse(resource)
+                .map_err(|e| map_err_generic(e, StatusCode::UNPROCESSABLE_ENTITY))?;
+            if acct.origin() != config.cached.acct_origin() {
+                return Ok(None);
+            }
+            UserFind::Username(acct.username())
+        }
+    };
+    container
+        .user
+        .find_local_user(find)
+        .await
+        .map_err(map_err_repository)
+}
+
+/// Checks whether the WebFinger resource points the instance actor,
+/// by its URL or `acct:{origin}@{origin}`.
+fn is_instance_resource(config: &Config, resource: &str) -> bool {
+    let instance_acct = format!(
+        "{}@{}",
+        instance_username(config),
+        config.cached.acct_origin()
+    );
+    let acct = resource.strip_prefix("acct:").unwrap_or(resource);
+    acct == instance_acct || resource == local_instance_actor_url(config).as_str()
+}
+
+fn instance_webfinger(config: &Config) -> WellknownWebfinger {
+    let actor_url = local_instance_actor_url(config);
+    WellknownWebfinger {
+        subject: format!(
+            "acct:{}@{}",
+            instance_username(config),
+            config.cached.acct_origin()
+        ),
+        aliases: vec![actor_url.clone()],
         links: vec![WellknownWebfingerLink {
             rel: "self".into(),
-            r#type: "application/activity+json".into(),
-            href: user_url,
+            r#type: Some(APPLICATION_ACTIVITY_JSON.into()),
+            href: Some(actor_url),
+            template: None,
         }],
-    };
-    let body = serde_json::to_string(&data).expect("JSON error");
-    Ok(Response::builder()
+    }
+}
+
+fn jrd_response(data: &WellknownWebfinger) -> Response<String> {
+    let body = serde_json::to_string(data).expect("JSON error");
+    Response::builder()
         .header(CONTENT_TYPE, "application/jrd+json; charset=UTF-8")
         .body(body)
-        .expect("cannot construct"))
+        .expect("cannot construct")
 }
 
 pub async fn wellknown_nodeinfo(
@@ -6,17 +6,30 @@ pub struct WebfingerQuery {
     pub resource: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthorizeInteractionQuery {
+    pub uri: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct WellknownWebfinger {
     pub subject: String,
+    pub aliases: Vec<Url>,
     pub links: Vec<WellknownWebfingerLink>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct WellknownWebfingerLink {
     pub rel: String,
-    pub r#type: String,
-    pub href: Url,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<Url>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
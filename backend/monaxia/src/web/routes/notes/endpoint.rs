@@ -0,0 +1,135 @@
+use crate::{
+    constant::ap::ACTIVITYSTREAMS_CONTEXT,
+    federation::{
+        local::local_note_url,
+        note::{is_audience, is_public, note_object},
+        reaction::reaction_collection,
+    },
+    web::{
+        error::{
+            bail_other, map_err_generic, map_err_repository, ErrorResponse, ErrorType, MxResult,
+        },
+        extract::{
+            ApAccept, ApDualAccept, ApJson, MustAcceptActivityJson, PathLocalNote, SignedFetch,
+        },
+        page::{note_page, vary_accept},
+        state::AppState,
+    },
+};
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use monaxia_data::{note::Note, reaction::ReactionKind};
+use monaxia_repository::repo::user::UserFind;
+use serde_json::Value as JsonValue;
+use url::Url;
+
+pub async fn note(
+    State(state): State<AppState>,
+    ApDualAccept(accept): ApDualAccept,
+    fetch: SignedFetch,
+    PathLocalNote(note): PathLocalNote,
+) -> MxResult<Response> {
+    ensure_readable(&state, &note, &fetch).await?;
+
+    let response = match accept {
+        ApAccept::ActivityJson => {
+            fetch.ensure_authorized()?;
+            let mut object = note_object(&state.config, &note);
+            object["@context"] = ACTIVITYSTREAMS_CONTEXT.into();
+            object["likes"] = reaction_collection_of(&state, &note, ReactionKind::Like).await?;
+            object["shares"] =
+                reaction_collection_of(&state, &note, ReactionKind::Announce).await?;
+            ApJson(object).into_response()
+        }
+        ApAccept::Html => {
+            let Some(author) = state
+                .container
+                .user
+                .find_local_user(UserFind::UserId(&note.user_id))
+                .await
+                .map_err(map_err_repository)?
+            else {
+                return bail_other(StatusCode::NOT_FOUND, "author not found");
+            };
+            note_page(&state.config, &author, &note).into_response_with(&state.config)
+        }
+    };
+    Ok(vary_accept(response))
+}
+
+pub async fn likes(
+    State(state): State<AppState>,
+    _: MustAcceptActivityJson,
+    fetch: SignedFetch,
+    PathLocalNote(note): PathLocalNote,
+) -> MxResult<ApJson<JsonValue>> {
+    reactions(&state, &fetch, &note, ReactionKind::Like).await
+}
+
+pub async fn shares(
+    State(state): State<AppState>,
+    _: MustAcceptActivityJson,
+    fetch: SignedFetch,
+    PathLocalNote(note): PathLocalNote,
+) -> MxResult<ApJson<JsonValue>> {
+    reactions(&state, &fetch, &note, ReactionKind::Announce).await
+}
+
+async fn reactions(
+    state: &AppState,
+    fetch: &SignedFetch,
+    note: &Note,
+    kind: ReactionKind,
+) -> MxResult<ApJson<JsonValue>> {
+    ensure_readable(state, note, fetch).await?;
+    fetch.ensure_authorized()?;
+
+    let mut collection = reaction_collection_of(state, note, kind).await?;
+    collection["@context"] = ACTIVITYSTREAMS_CONTEXT.into();
+    Ok(ApJson(collection))
+}
+
+/// Constructs `likes` or `shares` collection of the note.
+async fn reaction_collection_of(
+    state: &AppState,
+    note: &Note,
+    kind: ReactionKind,
+) -> MxResult<JsonValue> {
+    let note_url = local_note_url(&state.config, &note.id);
+    let total_items = state
+        .container
+        .reaction
+        .count_reactions(kind, note_url.as_str())
+        .await
+        .map_err(map_err_repository)?;
+    let name = match kind {
+        ReactionKind::Like => "likes",
+        ReactionKind::Announce => "shares",
+    };
+    let collection_url = Url::parse(&format!("{note_url}/{name}")).expect("URL error");
+    Ok(reaction_collection(&collection_url, total_items))
+}
+
+/// Non-public notes are exposed only to signed fetches of their audience.
+/// Others get 404 as if the note does not exist.
+async fn ensure_readable(state: &AppState, note: &Note, fetch: &SignedFetch) -> MxResult<()> {
+    let readable = match fetch {
+        _ if is_public(note) => true,
+        SignedFetch::Signed(signer) => is_audience(&state.config, &state.container, note, signer)
+            .await
+            .map_err(|e| map_err_generic(e, StatusCode::INTERNAL_SERVER_ERROR))?,
+        _ => false,
+    };
+    if !readable {
+        return Err(ErrorResponse {
+            status_code: StatusCode::NOT_FOUND,
+            error: ErrorType::NotFound,
+            reason: format!("note {} not found", note.id),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::{
+    federation::{
+        local::{local_note_url, local_user_url},
+        note::is_public,
+    },
+    web::{
+        error::{bail_other, MxResult},
+        extract::{ApAccept, ApDualAccept, PathLocalNote, PathVanityUser},
+        page::{note_page, profile_page, vary_accept},
+        state::AppState,
+    },
+};
+
+use axum::{
+    extract::State,
+    http::{header::LOCATION, StatusCode},
+    response::{IntoResponse, Response},
+};
+use url::Url;
+
+pub async fn profile(
+    State(state): State<AppState>,
+    ApDualAccept(accept): ApDualAccept,
+    PathVanityUser(local_user): PathVanityUser,
+) -> MxResult<Response> {
+    let response = match accept {
+        ApAccept::ActivityJson => redirect(local_user_url(&state.config, &local_user.id)),
+        ApAccept::Html => {
+            profile_page(&state.config, &local_user).into_response_with(&state.config)
+        }
+    };
+    Ok(vary_accept(response))
+}
+
+pub async fn profile_note(
+    State(state): State<AppState>,
+    ApDualAccept(accept): ApDualAccept,
+    PathVanityUser(local_user): PathVanityUser,
+    PathLocalNote(note): PathLocalNote,
+) -> MxResult<Response> {
+    if note.user_id != local_user.id || !is_public(&note) {
+        return bail_other(StatusCode::NOT_FOUND, format!("note {} not found", note.id));
+    }
+
+    let response = match accept {
+        ApAccept::ActivityJson => redirect(local_note_url(&state.config, &note.id)),
+        ApAccept::Html => {
+            note_page(&state.config, &local_user, &note).into_response_with(&state.config)
+        }
+    };
+    Ok(vary_accept(response))
+}
+
+/// Redirects to the canonical ID of the object.
+fn redirect(url: Url) -> Response {
+    (StatusCode::FOUND, [(LOCATION, url.to_string())]).into_response()
+}
@@ -1,62 +1,179 @@
-use super::schema::{ResponsePerson, ResponsePersonPublicKey};
+use super::schema::ResponsePerson;
 
-use crate::web::{
-    error::MxResult,
-    extract::{ApJson, MustAcceptActivityJson, PathLocalUser},
-    jsonld::JSONLD_OBJECT,
-    state::AppState,
+use crate::{
+    federation::{
+        actor::{local_actor, local_actor_key_only},
+        local::local_user_url,
+    },
+    web::{
+        collection::{
+            ordered_collection, ordered_collection_page, ordered_collection_total_only,
+            CollectionQuery,
+        },
+        error::{bail_other, map_err_repository, MxResult},
+        extract::{
+            ApAccept, ApDualAccept, ApJson, MustAcceptActivityJson, PathLocalUser, RjQuery,
+            SignedApJson, SignedFetch,
+        },
+        jsonld::JSONLD_OBJECT,
+        page::{profile_page, vary_accept},
+        routes::inbox::enqueue_activity,
+        state::AppState,
+    },
 };
 
-use axum::{extract::State, http::StatusCode};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::WithRejection;
+use monaxia_data::user::LocalUser;
+use monaxia_repository::repo::follow::FollowDirection;
+use serde_json::Value as JsonValue;
 
 pub async fn actor(
+    State(state): State<AppState>,
+    ApDualAccept(accept): ApDualAccept,
+    fetch: SignedFetch,
+    PathLocalUser(local_user): PathLocalUser,
+) -> MxResult<Response> {
+    let response = match accept {
+        ApAccept::ActivityJson => {
+            // public key must be always available to verify our signatures
+            let actor = if fetch.is_authorized() {
+                local_actor(&state.config, &local_user)
+            } else {
+                local_actor_key_only(&state.config, &local_user)
+            };
+            ApJson(ResponsePerson {
+                jsonld: JSONLD_OBJECT.clone(),
+                actor,
+            })
+            .into_response()
+        }
+        ApAccept::Html => {
+            profile_page(&state.config, &local_user).into_response_with(&state.config)
+        }
+    };
+    Ok(vary_accept(response))
+}
+
+pub async fn inbox(
+    State(state): State<AppState>,
+    PathLocalUser(local_user): PathLocalUser,
+    SignedApJson { signer, body }: SignedApJson<JsonValue>,
+) -> MxResult<StatusCode> {
+    enqueue_activity(&state, Some(local_user.id), signer, body).await
+}
+
+pub async fn outbox(
     State(state): State<AppState>,
     _: MustAcceptActivityJson,
+    fetch: SignedFetch,
     PathLocalUser(local_user): PathLocalUser,
-) -> MxResult<ApJson<ResponsePerson>> {
-    let base_url = state.config.cached.server_base_url();
-    let id_url = base_url
-        .join(&format!("/users/{}", local_user.id))
-        .expect("URL error");
-    let inbox_url = base_url
-        .join(&format!("/users/{}/inbox", local_user.id))
-        .expect("URL error");
-    let outbox_url = base_url
+    WithRejection(Query(query), _): RjQuery<CollectionQuery>,
+) -> MxResult<Response> {
+    fetch.ensure_authorized()?;
+    let outbox_url = state
+        .config
+        .cached
+        .server_base_url()
         .join(&format!("/users/{}/outbox", local_user.id))
         .expect("URL error");
-    let pubkey_id = {
-        let mut url = id_url.clone();
-        url.set_fragment(Some("main-key"));
-        url
-    };
 
-    Ok(ApJson(ResponsePerson {
-        jsonld: JSONLD_OBJECT.clone(),
-        id: id_url.to_string(),
-        preferred_username: local_user.username.clone(),
-        discoverable: true,
-        inbox: inbox_url,
-        outbox: outbox_url,
-        public_key: ResponsePersonPublicKey {
-            id: pubkey_id.to_string(),
-            owner: id_url.to_string(),
-            public_key_pem: local_user.public_key,
-        },
-    }))
+    if !query.page {
+        let total_items = state
+            .container
+            .activity
+            .count_outbox(&local_user.id)
+            .await
+            .map_err(map_err_repository)?;
+        return Ok(ApJson(ordered_collection(&outbox_url, total_items)).into_response());
+    }
+
+    let activities = state
+        .container
+        .activity
+        .fetch_outbox(&local_user.id, &query.pagination())
+        .await
+        .map_err(map_err_repository)?;
+    let items = activities.into_iter().map(|a| (a.id, a.body)).collect();
+    Ok(ApJson(ordered_collection_page(&outbox_url, &query, items)).into_response())
 }
 
-pub async fn inbox(
+pub async fn followers(
     State(state): State<AppState>,
-    // _: MustAcceptActivityJson,
+    _: MustAcceptActivityJson,
+    fetch: SignedFetch,
     PathLocalUser(local_user): PathLocalUser,
-) -> MxResult<(StatusCode, String)> {
-    Ok((StatusCode::NOT_IMPLEMENTED, "not implemented yet".into()))
+    WithRejection(Query(query), _): RjQuery<CollectionQuery>,
+) -> MxResult<Response> {
+    fetch.ensure_authorized()?;
+    follow_collection(&state, &local_user, FollowDirection::Followers, &query).await
 }
 
-pub async fn outbox(
+pub async fn following(
     State(state): State<AppState>,
-    // _: MustAcceptActivityJson,
+    _: MustAcceptActivityJson,
+    fetch: SignedFetch,
     PathLocalUser(local_user): PathLocalUser,
-) -> MxResult<(StatusCode, String)> {
-    Ok((StatusCode::NOT_IMPLEMENTED, "not implemented yet".into()))
+    WithRejection(Query(query), _): RjQuery<CollectionQuery>,
+) -> MxResult<Response> {
+    fetch.ensure_authorized()?;
+    follow_collection(&state, &local_user, FollowDirection::Following, &query).await
+}
+
+async fn follow_collection(
+    state: &AppState,
+    local_user: &LocalUser,
+    direction: FollowDirection,
+    query: &CollectionQuery,
+) -> MxResult<Response> {
+    let collection_name = match direction {
+        FollowDirection::Followers => "followers",
+        FollowDirection::Following => "following",
+    };
+    let collection_url = state
+        .config
+        .cached
+        .server_base_url()
+        .join(&format!("/users/{}/{collection_name}", local_user.id))
+        .expect("URL error");
+    let hidden = state.config.user.hide_follow_collections;
+
+    if !query.page {
+        let total_items = state
+            .container
+            .follow
+            .count_related(&local_user.id, direction)
+            .await
+            .map_err(map_err_repository)?;
+        let collection = if hidden {
+            ordered_collection_total_only(&collection_url, total_items)
+        } else {
+            ordered_collection(&collection_url, total_items)
+        };
+        return Ok(ApJson(collection).into_response());
+    }
+    if hidden {
+        return bail_other(StatusCode::FORBIDDEN, "this collection is hidden");
+    }
+
+    let related = state
+        .container
+        .follow
+        .fetch_related(&local_user.id, direction, &query.pagination())
+        .await
+        .map_err(map_err_repository)?;
+    let items = related
+        .into_iter()
+        .map(|r| {
+            let uri = r
+                .uri
+                .unwrap_or_else(|| local_user_url(&state.config, &r.user_id).to_string());
+            (r.follow_id, uri)
+        })
+        .collect();
+    Ok(ApJson(ordered_collection_page(&collection_url, query, items)).into_response())
 }
@@ -1,4 +1,7 @@
-use crate::repository_impl::construct_container_db;
+use crate::{
+    federation::{client::ApClient, instance::instance_client},
+    repository_impl::construct_container_db,
+};
 
 use std::sync::Arc;
 
@@ -13,15 +16,18 @@ pub struct AppState {
     pub config: Arc<Config>,
     pub producer: Producer<MxJob>,
     pub container: Container,
+    pub client: ApClient,
 }
 
 pub async fn construct_state(config: Config, producer: Producer<MxJob>) -> Result<AppState> {
     let container = construct_container_db(&config).await?;
+    let client = instance_client(&config, &container).await?;
 
     Ok(AppState {
         config: Arc::new(config),
         producer,
         container,
+        client,
     })
 }
 
@@ -32,10 +38,12 @@ pub fn construct_state_test() -> AppState {
     let (producer, _) = create_test_queues();
     let config = Default::default();
     let container = construct_container_test();
+    let client = ApClient::new(&config).expect("failed to construct client");
 
     AppState {
         config: Arc::new(config),
         producer,
         container,
+        client,
     }
 }
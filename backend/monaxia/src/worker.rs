@@ -1,4 +1,15 @@
+mod delete;
+mod deliver;
+mod follow;
+mod inbox;
+mod moving;
+mod reaction;
 mod root;
+mod update;
+
+use crate::federation::client::ApClient;
+
+use std::sync::Arc;
 
 use anyhow::Result;
 use lapin::{Connection as LapinConnection, ConnectionProperties};
@@ -8,14 +19,24 @@ use monaxia_queue::{
     job::{Consumer, Producer},
     queue::amqp::{create_amqp_consumer, create_amqp_producer},
 };
+use monaxia_repository::Container;
 use tokio::spawn;
 use tracing::info;
 
-pub async fn spawn_workers(consumers: Vec<Consumer<MxJob>>) {
+/// Shared resources for workers.
+#[derive(Clone)]
+pub struct WorkerState {
+    pub config: Arc<Config>,
+    pub producer: Producer<MxJob>,
+    pub container: Container,
+    pub client: ApClient,
+}
+
+pub async fn spawn_workers(state: WorkerState, consumers: Vec<Consumer<MxJob>>) {
     info!("spawning {} workers", consumers.len());
 
     for consumer in consumers {
-        spawn(root::worker(consumer));
+        spawn(root::worker(state.clone(), consumer));
     }
 }
 
@@ -27,6 +48,14 @@ pub async fn create_queues(config: &Config) -> Result<(Producer<MxJob>, Vec<Cons
     Ok((producer, consumers))
 }
 
+/// Creates a producer only, for processes which do not consume jobs.
+pub async fn create_producer(config: &Config) -> Result<Producer<MxJob>> {
+    let conn = LapinConnection::connect(&config.queue.url, ConnectionProperties::default()).await?;
+    let producer = create_amqp_producer(&conn, "producer").await?;
+
+    Ok(producer)
+}
+
 #[cfg(test)]
 pub fn create_test_queues() -> (Producer<MxJob>, Consumer<MxJob>) {
     use futures::channel::mpsc::channel;
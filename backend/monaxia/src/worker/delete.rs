@@ -0,0 +1,46 @@
+use super::{root::InvalidActivity, WorkerState};
+use crate::federation::{json::object_id, local::local_note_url};
+
+use anyhow::{bail, Context, Result};
+use monaxia_data::{ap::acct_origin_of, user::RemoteUser};
+use serde_json::Value as JsonValue;
+use tracing::info;
+use url::Url;
+
+/// Purges the remote user deleting themselves, or forgets reactions of local users
+/// to the deleted remote object.
+pub async fn receive_delete(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+) -> Result<()> {
+    let object = activity
+        .get("object")
+        .and_then(object_id)
+        .context(InvalidActivity("Delete must have object".into()))?;
+    if object == signer.uri {
+        let notes_url = local_note_url(&state.config, "");
+        state
+            .container
+            .user
+            .remove_user(&signer.id, notes_url.as_str())
+            .await?;
+        info!("remote user {} was deleted, purged", signer.uri);
+        return Ok(());
+    }
+
+    let object_origin = Url::parse(object).ok().and_then(|u| acct_origin_of(&u));
+    let signer_origin = Url::parse(&signer.uri)
+        .ok()
+        .and_then(|u| acct_origin_of(&u));
+    if object_origin.is_none() || object_origin != signer_origin {
+        bail!(InvalidActivity(format!(
+            "{} cannot delete {object}",
+            signer.uri
+        )));
+    }
+
+    state.container.reaction.remove_reactions_to(object).await?;
+    info!("{} deleted {object}", signer.uri);
+    Ok(())
+}
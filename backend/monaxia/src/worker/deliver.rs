@@ -0,0 +1,34 @@
+use super::{root::JobError, WorkerState};
+use crate::federation::{delivery::deliver_activity, FederationError};
+
+use tracing::info;
+
+/// Delivers an activity to the remote inbox.
+pub async fn deliver(
+    state: &WorkerState,
+    actor_id: &str,
+    inbox: &str,
+    activity: &str,
+) -> Result<(), JobError> {
+    let result = deliver_activity(
+        &state.config,
+        &state.container,
+        &state.client,
+        actor_id,
+        inbox,
+        activity,
+    )
+    .await;
+    match result {
+        Ok(()) => (),
+        Err(FederationError::Suspended(domain)) => {
+            info!("domain {domain} is suspended, skipping delivery to {inbox}");
+            return Ok(());
+        }
+        Err(e) if e.is_transient() => return Err(JobError::Transient(e.into())),
+        Err(e) => return Err(JobError::Permanent(e.into())),
+    }
+
+    info!("delivered activity to {inbox}");
+    Ok(())
+}
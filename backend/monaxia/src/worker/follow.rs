@@ -0,0 +1,162 @@
+use super::{root::InvalidActivity, WorkerState};
+use crate::federation::{
+    follow::{accept_follow, hold_follow},
+    json::{object_id, object_type},
+    local::local_user_id_of,
+};
+
+use anyhow::{bail, Context, Result};
+use monaxia_data::{domain::DomainPolicy, follow::FollowState, user::RemoteUser};
+use monaxia_repository::repo::{follow::FollowFind, user::UserFind};
+use serde_json::Value as JsonValue;
+use tracing::{info, warn};
+
+/// Accepts Follow to a local user, or holds it if the domain of the follower is silenced
+/// or the local user approves followers manually.
+/// Follow from an already accepted follower is accepted again.
+pub async fn receive_follow(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+) -> Result<()> {
+    let object = activity
+        .get("object")
+        .and_then(object_id)
+        .context(InvalidActivity("Follow must have object".into()))?;
+    let Some(user_id) = local_user_id_of(&state.config, object) else {
+        warn!("Follow object {object} is not a local user, ignoring");
+        return Ok(());
+    };
+    let local_user = state
+        .container
+        .user
+        .find_local_user(UserFind::UserId(user_id))
+        .await?
+        .with_context(|| InvalidActivity(format!("local user {user_id} not found")))?;
+
+    let policy = state.container.domain.find_policy(&signer.domain).await?;
+    let accepted = state
+        .container
+        .follow
+        .find_follow(FollowFind::Pair {
+            follower_id: &signer.id,
+            followee_id: &local_user.id,
+        })
+        .await?
+        .is_some_and(|f| f.state == FollowState::Accepted);
+    if !accepted
+        && (policy == DomainPolicy::Silence || local_user.profile.manually_approves_followers)
+    {
+        hold_follow(&state.container, &local_user, signer, activity).await?;
+        info!(
+            "{} requested to follow {}, held for approval",
+            signer.uri, local_user.username
+        );
+        return Ok(());
+    }
+
+    accept_follow(
+        &state.config,
+        &state.container,
+        &state.producer,
+        &local_user,
+        signer,
+        activity,
+    )
+    .await?;
+    info!("{} followed {}", signer.uri, local_user.username);
+    Ok(())
+}
+
+/// Marks Follow sent by a local user as accepted.
+pub async fn receive_accept(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+) -> Result<()> {
+    let Some(follow_id) = followee_follow_id(state, signer, activity).await? else {
+        return Ok(());
+    };
+    state.container.follow.accept_follow(&follow_id).await?;
+    info!("{} accepted follow", signer.uri);
+    Ok(())
+}
+
+/// Removes Follow sent by a local user.
+pub async fn receive_reject(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+) -> Result<()> {
+    let Some(follow_id) = followee_follow_id(state, signer, activity).await? else {
+        return Ok(());
+    };
+    state.container.follow.remove_follow(&follow_id).await?;
+    info!("{} rejected follow", signer.uri);
+    Ok(())
+}
+
+/// Removes the relationship established by Follow from the remote user.
+pub async fn receive_undo_follow(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    follow: &JsonValue,
+) -> Result<()> {
+    let follow_activity_id =
+        object_id(follow).context(InvalidActivity("Follow must have id".into()))?;
+    let Some(follow) = state
+        .container
+        .follow
+        .find_follow(FollowFind::ActivityId(follow_activity_id))
+        .await?
+    else {
+        info!("unknown Follow {follow_activity_id}, ignoring");
+        return Ok(());
+    };
+    if follow.follower_id != signer.id {
+        bail!(InvalidActivity(format!(
+            "{} cannot undo Follow {follow_activity_id}",
+            signer.uri
+        )));
+    }
+
+    state.container.follow.remove_follow(&follow.id).await?;
+    info!("{} undid follow", signer.uri);
+    Ok(())
+}
+
+/// Finds the follow relationship responded by Accept or Reject.
+/// The signer must be the followee.
+async fn followee_follow_id(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+) -> Result<Option<String>> {
+    let object = activity
+        .get("object")
+        .context(InvalidActivity("activity must have object".into()))?;
+    if let Some(object_type) = object_type(object).filter(|t| *t != "Follow") {
+        info!("response for {object_type} is not supported yet, ignoring");
+        return Ok(None);
+    }
+
+    let follow_activity_id =
+        object_id(object).context(InvalidActivity("Follow must have id".into()))?;
+    let Some(follow) = state
+        .container
+        .follow
+        .find_follow(FollowFind::ActivityId(follow_activity_id))
+        .await?
+    else {
+        info!("unknown Follow {follow_activity_id}, ignoring");
+        return Ok(None);
+    };
+    if follow.followee_id != signer.id {
+        bail!(InvalidActivity(format!(
+            "{} cannot respond to Follow {follow_activity_id}",
+            signer.uri
+        )));
+    }
+
+    Ok(Some(follow.id))
+}
@@ -0,0 +1,119 @@
+use super::{
+    delete::receive_delete,
+    follow::{receive_accept, receive_follow, receive_reject, receive_undo_follow},
+    moving::receive_move,
+    reaction::{receive_reaction, receive_undo_reaction},
+    root::InvalidActivity,
+    update::receive_update,
+    WorkerState,
+};
+use crate::federation::json::{object_id, object_type};
+
+use anyhow::{Context, Result};
+use monaxia_data::{domain::DomainPolicy, reaction::ReactionKind};
+use monaxia_repository::repo::{follow::FollowFind, reaction::ReactionFind, user::RemoteUserFind};
+use serde_json::Value as JsonValue;
+use tracing::{debug, info, warn};
+
+/// Processes an activity received in inbox.
+/// `signer` has been verified as the actor of the activity, which has been canonicalized.
+pub async fn process_inbox(
+    state: &WorkerState,
+    recipient: Option<&str>,
+    signer: &str,
+    activity: &str,
+) -> Result<()> {
+    let activity: JsonValue = serde_json::from_str(activity)?;
+    let activity_type =
+        object_type(&activity).context(InvalidActivity("activity must have type".into()))?;
+    debug!("processing {activity_type} from {signer} (recipient: {recipient:?})");
+
+    let signer = state
+        .container
+        .user
+        .find_remote_user(RemoteUserFind::Uri(signer))
+        .await?
+        .with_context(|| InvalidActivity(format!("signer {signer} is not registered")))?;
+    let policy = state.container.domain.find_policy(&signer.domain).await?;
+    if policy == DomainPolicy::Suspend {
+        info!(
+            "domain {} is suspended, dropping {activity_type}",
+            signer.domain
+        );
+        return Ok(());
+    }
+
+    match activity_type {
+        "Follow" => receive_follow(state, &signer, &activity).await?,
+        "Accept" => receive_accept(state, &signer, &activity).await?,
+        "Reject" => receive_reject(state, &signer, &activity).await?,
+        "Delete" => receive_delete(state, &signer, &activity).await?,
+        "Like" => receive_reaction(state, &signer, &activity, ReactionKind::Like).await?,
+        "Update" => receive_update(state, &signer, &activity).await?,
+        "Move" => receive_move(state, &signer, &activity).await?,
+        "Announce" => receive_reaction(state, &signer, &activity, ReactionKind::Announce).await?,
+        "Undo" => {
+            let object = activity
+                .get("object")
+                .context(InvalidActivity("Undo must have object".into()))?;
+            let undone_type = match object_type(object) {
+                Some(undone_type) => Some(undone_type),
+                None => {
+                    let undone_id = object_id(object)
+                        .context(InvalidActivity("Undo must have object".into()))?;
+                    referenced_activity_type(state, undone_id).await?
+                }
+            };
+            match undone_type {
+                Some("Follow") => receive_undo_follow(state, &signer, object).await?,
+                Some("Like") => {
+                    receive_undo_reaction(state, &signer, object, ReactionKind::Like).await?
+                }
+                Some("Announce") => {
+                    receive_undo_reaction(state, &signer, object, ReactionKind::Announce).await?
+                }
+                Some(object_type) => {
+                    info!("Undo({object_type}) is not supported yet, ignoring");
+                }
+                None => info!("unknown activity {object} to undo, ignoring"),
+            }
+        }
+        "Create" | "Block" | "Flag" => {
+            info!("{activity_type} activity is not supported yet, ignoring");
+        }
+        otherwise => {
+            warn!("unknown activity type {otherwise}, ignoring");
+        }
+    }
+
+    Ok(())
+}
+
+/// Determines the type of the activity which Undo refers by IRI.
+/// Ownership is checked by each Undo handler.
+async fn referenced_activity_type(
+    state: &WorkerState,
+    activity_id: &str,
+) -> Result<Option<&'static str>> {
+    let follow = state
+        .container
+        .follow
+        .find_follow(FollowFind::ActivityId(activity_id))
+        .await?;
+    if follow.is_some() {
+        return Ok(Some("Follow"));
+    }
+
+    for kind in [ReactionKind::Like, ReactionKind::Announce] {
+        let reaction = state
+            .container
+            .reaction
+            .find_reaction(kind, ReactionFind::ActivityId(activity_id))
+            .await?;
+        if reaction.is_some() {
+            return Ok(Some(kind.as_str()));
+        }
+    }
+
+    Ok(None)
+}
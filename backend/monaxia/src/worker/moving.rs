@@ -0,0 +1,58 @@
+use super::{root::InvalidActivity, WorkerState};
+use crate::federation::{
+    json::object_id,
+    local::local_user_id_of,
+    moving::{transfer_follows, verify_move_target},
+};
+
+use anyhow::{bail, Context, Result};
+use monaxia_data::user::RemoteUser;
+use serde_json::Value as JsonValue;
+use tracing::info;
+use url::Url;
+
+/// Moves follows of local users to the target, after verifying that the target
+/// lists the signer in `alsoKnownAs`.
+pub async fn receive_move(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+) -> Result<()> {
+    let object = activity
+        .get("object")
+        .and_then(object_id)
+        .context(InvalidActivity("Move must have object".into()))?;
+    if object != signer.uri {
+        bail!(InvalidActivity(format!(
+            "{} cannot move {object}",
+            signer.uri
+        )));
+    }
+    let target_uri = activity
+        .get("target")
+        .and_then(object_id)
+        .context(InvalidActivity("Move must have target".into()))?;
+    if local_user_id_of(&state.config, target_uri).is_some() {
+        bail!(InvalidActivity(format!(
+            "moving to local user {target_uri} is not supported"
+        )));
+    }
+    let target_uri = Url::parse(target_uri)
+        .with_context(|| InvalidActivity(format!("invalid Move target {target_uri}")))?;
+
+    let target =
+        verify_move_target(&state.container, &state.client, &signer.uri, &target_uri).await?;
+    let transferred = transfer_follows(
+        &state.config,
+        &state.container,
+        &state.producer,
+        signer,
+        &target,
+    )
+    .await?;
+    info!(
+        "{} moved to {}, transferred {transferred} follows",
+        signer.uri, target.uri
+    );
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use super::{root::InvalidActivity, WorkerState};
+use crate::federation::{
+    json::object_id,
+    local::{local_note_id_of, local_note_url},
+    note::{is_audience, is_public},
+};
+
+use anyhow::{bail, Context, Result};
+use monaxia_data::{
+    reaction::{ReactionKind, ReactionRegistration},
+    user::RemoteUser,
+};
+use monaxia_repository::repo::reaction::ReactionFind;
+use serde_json::Value as JsonValue;
+use tracing::info;
+
+/// Records Like or Announce of a local note by the remote user.
+pub async fn receive_reaction(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+    kind: ReactionKind,
+) -> Result<()> {
+    let activity_id =
+        object_id(activity).context(InvalidActivity("activity must have id".into()))?;
+    let actor = activity
+        .get("actor")
+        .and_then(object_id)
+        .context(InvalidActivity("activity must have actor".into()))?;
+    if actor != signer.uri {
+        bail!(InvalidActivity(format!(
+            "{} cannot send {} as {actor}",
+            signer.uri,
+            kind.as_str()
+        )));
+    }
+    let object = activity
+        .get("object")
+        .and_then(object_id)
+        .context(InvalidActivity("activity must have object".into()))?;
+    let Some(note_id) = local_note_id_of(&state.config, object) else {
+        info!("{} of non-local object {object}, ignoring", kind.as_str());
+        return Ok(());
+    };
+    let Some(note) = state.container.note.find_note(note_id).await? else {
+        info!("unknown note {note_id}, ignoring");
+        return Ok(());
+    };
+    if kind == ReactionKind::Announce && !is_public(&note) {
+        info!("non-public note {note_id} cannot be announced, ignoring");
+        return Ok(());
+    }
+    if !is_public(&note) && !is_audience(&state.config, &state.container, &note, signer).await? {
+        info!(
+            "{} is not an audience of note {note_id}, ignoring",
+            signer.uri
+        );
+        return Ok(());
+    }
+
+    state
+        .container
+        .reaction
+        .register_reaction(ReactionRegistration {
+            kind,
+            user_id: signer.id.clone(),
+            object_uri: local_note_url(&state.config, &note.id).to_string(),
+            activity_id: activity_id.to_string(),
+            author_uri: None,
+            author_inbox: None,
+        })
+        .await?;
+    info!("{} sent {} to note {note_id}", signer.uri, kind.as_str());
+    Ok(())
+}
+
+/// Removes Like or Announce by the remote user.
+pub async fn receive_undo_reaction(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    reaction: &JsonValue,
+    kind: ReactionKind,
+) -> Result<()> {
+    let activity_id =
+        object_id(reaction).context(InvalidActivity("reaction must have id".into()))?;
+    let Some(reaction) = state
+        .container
+        .reaction
+        .find_reaction(kind, ReactionFind::ActivityId(activity_id))
+        .await?
+    else {
+        info!("unknown {} {activity_id}, ignoring", kind.as_str());
+        return Ok(());
+    };
+    if reaction.user_id != signer.id {
+        bail!(InvalidActivity(format!(
+            "{} cannot undo {activity_id}",
+            signer.uri
+        )));
+    }
+
+    state
+        .container
+        .reaction
+        .remove_reaction(kind, &reaction.id)
+        .await?;
+    info!("{} undid {}", signer.uri, kind.as_str());
+    Ok(())
+}
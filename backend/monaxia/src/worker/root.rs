@@ -1,16 +1,42 @@
-use anyhow::Result;
+use super::{deliver::deliver, inbox::process_inbox, WorkerState};
+use crate::federation::FederationError;
+
+use anyhow::{Error as AnyhowError, Result};
 use monaxia_job::job::{Job, MxJob};
 use monaxia_queue::job::Consumer;
+use thiserror::Error as ThisError;
 use tracing::{error, info};
 
-pub async fn worker(consumer: Consumer<MxJob>) -> Result<()> {
+/// Failure of a job.
+#[derive(Debug, ThisError)]
+pub enum JobError {
+    /// The job may succeed if retried.
+    #[error("{0}")]
+    Transient(#[from] AnyhowError),
+
+    /// The job never succeeds; no more retry.
+    #[error("{0} (permanent)")]
+    Permanent(AnyhowError),
+}
+
+/// Activity which is malformed or not permitted for the signer.
+/// Processing it never succeeds, so it is not retried.
+#[derive(Debug, ThisError)]
+#[error("invalid activity: {0}")]
+pub struct InvalidActivity(pub String);
+
+pub async fn worker(state: WorkerState, consumer: Consumer<MxJob>) -> Result<()> {
     // TODO: just loop
     while let Some((job, tag)) = consumer.fetch().await? {
-        match do_job(job.job().clone(), job.tag().to_string()).await {
+        match do_job(&state, job.job().clone(), job.tag().to_string()).await {
             Ok(()) => {
                 consumer.mark_success(tag).await?;
             }
-            Err(e) => {
+            Err(e @ JobError::Permanent(_)) => {
+                error!("job error: {e}");
+                consumer.mark_failure(tag).await?;
+            }
+            Err(e @ JobError::Transient(_)) => {
                 error!("job error: {e}");
                 consumer.mark_failure(tag).await?;
                 if let Some((data, delay)) = job.next() {
@@ -23,12 +49,69 @@ pub async fn worker(consumer: Consumer<MxJob>) -> Result<()> {
     Ok(())
 }
 
-async fn do_job(job: Job, _tag: String) -> Result<()> {
+async fn do_job(state: &WorkerState, job: Job, _tag: String) -> Result<(), JobError> {
     match job {
         Job::Hello => {
             info!("hello monaxia!");
         }
+        Job::ProcessInbox {
+            recipient,
+            signer,
+            activity,
+        } => {
+            process_inbox(state, recipient.as_deref(), &signer, &activity)
+                .await
+                .map_err(classify_inbox_error)?;
+        }
+        Job::Deliver {
+            actor_id,
+            inbox,
+            activity,
+        } => {
+            deliver(state, &actor_id, &inbox, &activity).await?;
+        }
     }
 
     Ok(())
 }
+
+/// Invalid activities and permanent federation errors are not retried.
+fn classify_inbox_error(err: AnyhowError) -> JobError {
+    let permanent = err.is::<InvalidActivity>()
+        || err
+            .downcast_ref::<FederationError>()
+            .is_some_and(|e| !e.is_transient());
+    if permanent {
+        JobError::Permanent(err)
+    } else {
+        JobError::Transient(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_inbox_error, InvalidActivity, JobError};
+    use crate::federation::FederationError;
+
+    use anyhow::{anyhow, Context, Result};
+
+    fn is_permanent(result: Result<()>) -> bool {
+        matches!(
+            classify_inbox_error(result.expect_err("must fail")),
+            JobError::Permanent(_)
+        )
+    }
+
+    #[test]
+    fn classify_inbox_error_works() {
+        let missing = None::<()>.context(InvalidActivity("missing".into()));
+        let forbidden = Err(anyhow!(InvalidActivity("forbidden".into())));
+        let invalid = Err(FederationError::InvalidObject("invalid".into()).into());
+        let unknown = Err(anyhow!("unknown failure"));
+
+        assert!(is_permanent(missing));
+        assert!(is_permanent(forbidden));
+        assert!(is_permanent(invalid));
+        assert!(!is_permanent(unknown));
+    }
+}
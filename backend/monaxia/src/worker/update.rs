@@ -0,0 +1,30 @@
+use super::{root::InvalidActivity, WorkerState};
+use crate::federation::{json::object_id, resolver::fetch_actor};
+
+use anyhow::{Context, Result};
+use monaxia_data::user::RemoteUser;
+use serde_json::Value as JsonValue;
+use tracing::info;
+use url::Url;
+
+/// Refreshes the remote user updating themselves.
+/// The actor is refetched instead of trusting the embedded object.
+pub async fn receive_update(
+    state: &WorkerState,
+    signer: &RemoteUser,
+    activity: &JsonValue,
+) -> Result<()> {
+    let object = activity
+        .get("object")
+        .and_then(object_id)
+        .context(InvalidActivity("Update must have object".into()))?;
+    if object != signer.uri {
+        info!("Update of {object} is not supported yet, ignoring");
+        return Ok(());
+    }
+
+    let actor_uri = Url::parse(&signer.uri)?;
+    fetch_actor(&state.container, &state.client, &actor_uri).await?;
+    info!("remote user {} was updated, refreshed", signer.uri);
+    Ok(())
+}